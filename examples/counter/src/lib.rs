@@ -1,38 +1,29 @@
 use localghost::prelude::*;
-use localghost::dom::{self, Element, query_selector};
+use localghost::dom::{self, query_selector};
+use localghost::html;
 
-use std::cell::RefCell;
+use std::cell::Cell;
 use std::rc::Rc;
 
 #[localghost::main]
 async fn main() {
-    // TODO: this should be part of the macro.
-    localghost::log::Logger::new().start().unwrap_throw();
+    let counter = Rc::new(Cell::new(0isize));
 
-    let counter = Rc::new(RefCell::new(0isize));
-    let body = dom::body();
+    let update = |delta: isize| {
+        let counter = counter.clone();
+        move |_| {
+            counter.set(counter.get() + delta);
+            let el = query_selector("#counter").unwrap_throw();
+            el.set_text(&counter.get().to_string());
+        }
+    };
 
-    let button = Element::with_text("button", "+");
-    let count_handle = counter.clone();
-    button.on_with("click", move |_| {
-        let mut handle = count_handle.borrow_mut();
-        *handle += 1;
-        let el = query_selector("#counter").unwrap_throw();
-        el.set_text(&format!("{}", *handle))
-    });
-    body.append(button);
-
-    let p = Element::with_text("p", &format!("{}", counter.borrow()));
-    p.set_attr("id", "counter");
-    body.append(p);
-
-    let button = Element::with_text("button", "-");
-    let count_handle = counter.clone();
-    button.on_with("click", move |_| {
-        let mut handle = count_handle.borrow_mut();
-        *handle -= 1;
-        let el = query_selector("#counter").unwrap_throw();
-        el.set_text(&format!("{}", *handle));
-    });
-    body.append(button);
+    let el = html! {
+        <div>
+            <button on:click={update(1)}>"+"</button>
+            <p id="counter">{counter.get()}</p>
+            <button on:click={update(-1)}>"-"</button>
+        </div>
+    };
+    dom::body().append(el);
 }
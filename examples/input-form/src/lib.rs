@@ -1,6 +1,5 @@
-use localghost::dom::{query_selector, self, Element};
+use localghost::dom::{query_selector, self, Element, Input};
 use localghost::prelude::*;
-use localghost::raw::web_sys;
 
 #[localghost::main]
 async fn main() {
@@ -8,11 +7,11 @@ async fn main() {
     let body = dom::body();
 
     // Create an `<input>` field
-    let input = Element::new("input");
+    let input = Input::new();
     input.set_attr("type", "text");
     input.set_attr("placeholder", "What's your name?");
     input.on_with("input", |ev| {
-        if let Some(target) = ev.target::<web_sys::HtmlInputElement>() {
+        if let Some(Ok(target)) = ev.target_element().map(Element::downcast::<Input>) {
             let el = query_selector("#text").unwrap_throw();
             el.set_text(&target.value());
         }
@@ -0,0 +1,258 @@
+//! Parsing and expansion of the `html!` macro.
+
+use proc_macro2::{Span, TokenStream};
+use quote::{quote, quote_spanned};
+use syn::ext::IdentExt;
+use syn::parse::{Parse, ParseStream};
+use syn::spanned::Spanned;
+use syn::{braced, Error, Expr, Ident, LitStr, Result, Token};
+
+/// The input of the macro: a single root element.
+pub(crate) struct Html {
+    root: Element,
+}
+
+impl Html {
+    /// Expand into an expression which builds the element.
+    pub(crate) fn expand(&self) -> TokenStream {
+        self.root.expand()
+    }
+}
+
+impl Parse for Html {
+    fn parse(input: ParseStream<'_>) -> Result<Self> {
+        if input.is_empty() {
+            return Err(Error::new(
+                Span::call_site(),
+                "expected an element, such as `<div></div>`",
+            ));
+        }
+        let root = match input.parse()? {
+            Node::Element(el) => el,
+            node => {
+                return Err(Error::new(
+                    node.span(),
+                    "the root of `html!` must be an element",
+                ))
+            }
+        };
+        if !input.is_empty() {
+            return Err(input.error("`html!` must contain a single root element"));
+        }
+        Ok(Self { root })
+    }
+}
+
+/// A child of an element.
+enum Node {
+    Element(Element),
+    Text(LitStr),
+    Expr(Expr),
+}
+
+impl Node {
+    fn span(&self) -> Span {
+        match self {
+            Node::Element(el) => el.name.span,
+            Node::Text(lit) => lit.span(),
+            Node::Expr(expr) => expr.span(),
+        }
+    }
+}
+
+impl Parse for Node {
+    fn parse(input: ParseStream<'_>) -> Result<Self> {
+        if input.peek(Token![<]) {
+            Ok(Node::Element(input.parse()?))
+        } else if input.peek(LitStr) {
+            Ok(Node::Text(input.parse()?))
+        } else if input.peek(syn::token::Brace) {
+            Ok(Node::Expr(parse_braced(input)?))
+        } else {
+            Err(input.error("expected an element, a string literal or a `{ .. }` expression"))
+        }
+    }
+}
+
+/// An element, its attributes, and its children.
+struct Element {
+    name: Name,
+    attrs: Vec<Attr>,
+    children: Vec<Node>,
+}
+
+impl Element {
+    fn expand(&self) -> TokenStream {
+        let el = Ident::new("element", Span::mixed_site());
+        let kind = &self.name.value;
+        let new = quote_spanned! { self.name.span =>
+            let #el = ::localghost::dom::Element::new(#kind);
+        };
+
+        let attrs = self.attrs.iter().map(|attr| {
+            let name = &attr.name.value;
+            match &attr.value {
+                Value::Text(lit) if attr.is_event => quote_spanned! { lit.span() =>
+                    compile_error!("event handlers must be a `{ .. }` expression");
+                },
+                Value::Expr(expr) if attr.is_event => quote_spanned! { expr.span() =>
+                    ::localghost::events::EventTarget::on_with(&#el, #name, #expr).attach();
+                },
+                Value::Text(lit) => quote_spanned! { lit.span() =>
+                    #el.set_attr(#name, #lit);
+                },
+                Value::Expr(expr) => quote_spanned! { expr.span() =>
+                    #el.set_attr(#name, &::std::string::ToString::to_string(&(#expr)));
+                },
+            }
+        });
+
+        let children = self.children.iter().map(|child| match child {
+            Node::Element(child) => {
+                let child = child.expand();
+                quote! { #el.append(#child); }
+            }
+            Node::Text(lit) => quote_spanned! { lit.span() =>
+                #el.append(::localghost::dom::Text::new(#lit));
+            },
+            Node::Expr(expr) => quote_spanned! { expr.span() =>
+                ::localghost::dom::Child::append_to(#expr, &#el);
+            },
+        });
+
+        quote! {{
+            #new
+            #(#attrs)*
+            #(#children)*
+            #el
+        }}
+    }
+}
+
+impl Parse for Element {
+    fn parse(input: ParseStream<'_>) -> Result<Self> {
+        input.parse::<Token![<]>()?;
+        let name: Name = input.parse()?;
+
+        let mut attrs = vec![];
+        while !input.peek(Token![>]) && !input.peek(Token![/]) {
+            if input.is_empty() {
+                return Err(Error::new(
+                    name.span,
+                    format!("unclosed tag `<{}`", name.value),
+                ));
+            }
+            attrs.push(input.parse()?);
+        }
+
+        // Self-closing element, such as `<br />`.
+        if input.peek(Token![/]) {
+            input.parse::<Token![/]>()?;
+            input.parse::<Token![>]>()?;
+            return Ok(Self {
+                name,
+                attrs,
+                children: vec![],
+            });
+        }
+        input.parse::<Token![>]>()?;
+
+        let mut children = vec![];
+        while !(input.peek(Token![<]) && input.peek2(Token![/])) {
+            if input.is_empty() {
+                return Err(Error::new(
+                    name.span,
+                    format!("unclosed element `<{}>`", name.value),
+                ));
+            }
+            children.push(input.parse()?);
+        }
+
+        input.parse::<Token![<]>()?;
+        input.parse::<Token![/]>()?;
+        let close: Name = input.parse()?;
+        if close.value != name.value {
+            return Err(Error::new(
+                close.span,
+                format!("expected `</{}>`", name.value),
+            ));
+        }
+        input.parse::<Token![>]>()?;
+
+        Ok(Self {
+            name,
+            attrs,
+            children,
+        })
+    }
+}
+
+/// An attribute such as `class="x"`, or an event handler such as
+/// `on:click={handler}`.
+struct Attr {
+    name: Name,
+    is_event: bool,
+    value: Value,
+}
+
+impl Parse for Attr {
+    fn parse(input: ParseStream<'_>) -> Result<Self> {
+        let mut name: Name = input.parse()?;
+        let is_event = name.value == "on" && input.peek(Token![:]);
+        if is_event {
+            input.parse::<Token![:]>()?;
+            name = input.parse()?;
+        }
+        input.parse::<Token![=]>()?;
+        let value = if input.peek(LitStr) {
+            Value::Text(input.parse()?)
+        } else if input.peek(syn::token::Brace) {
+            Value::Expr(parse_braced(input)?)
+        } else {
+            return Err(input.error("expected a string literal or a `{ .. }` expression"));
+        };
+        Ok(Self {
+            name,
+            is_event,
+            value,
+        })
+    }
+}
+
+/// The value of an attribute.
+enum Value {
+    Text(LitStr),
+    Expr(Expr),
+}
+
+/// A tag or attribute name, which may contain dashes and keywords, such as
+/// `data-type`.
+struct Name {
+    value: String,
+    span: Span,
+}
+
+impl Parse for Name {
+    fn parse(input: ParseStream<'_>) -> Result<Self> {
+        let ident = Ident::parse_any(input)?;
+        let span = ident.span();
+        let mut value = ident.unraw().to_string();
+        while input.peek(Token![-]) {
+            input.parse::<Token![-]>()?;
+            value.push('-');
+            value.push_str(&Ident::parse_any(input)?.unraw().to_string());
+        }
+        Ok(Self { value, span })
+    }
+}
+
+/// Parse an expression wrapped in braces.
+fn parse_braced(input: ParseStream<'_>) -> Result<Expr> {
+    let content;
+    braced!(content in input);
+    let expr = content.parse()?;
+    if !content.is_empty() {
+        return Err(content.error("expected a single expression"));
+    }
+    Ok(expr)
+}
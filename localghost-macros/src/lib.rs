@@ -1,4 +1,4 @@
-//! Async fn main support for WASM.
+//! Async fn main support and declarative markup for WASM.
 
 #![forbid(unsafe_code, future_incompatible, rust_2018_idioms)]
 #![deny(missing_debug_implementations, nonstandard_style)]
@@ -8,6 +8,8 @@ use proc_macro::TokenStream;
 use quote::{quote, quote_spanned};
 use syn::spanned::Spanned;
 
+mod html;
+
 /// Initializes an `async` main function.
 ///
 /// This enables the use of `async/.await` from the root of the program, logs any
@@ -67,3 +69,41 @@ pub fn main(_attr: TokenStream, item: TokenStream) -> TokenStream {
 
     result.into()
 }
+
+/// Build a `dom::Element` from JSX-like markup.
+///
+/// Attribute values and children can be string literals, or Rust expressions
+/// wrapped in braces. Expressions used as attributes are converted with
+/// `ToString`, and expressions used as children are appended through the
+/// `dom::Child` trait. Event handlers are registered with `on:<event>`, and
+/// stay registered for as long as the element exists.
+///
+/// Event handlers are attached with `EventListener::attach`, so their memory
+/// is only reclaimed when weak references are enabled, by building with
+/// `wasm-bindgen --weak-refs`. Without them, every evaluation of `html!` with
+/// event handlers leaks those handlers. Prefer `dom::VDom` for trees which
+/// are rendered over and over.
+///
+/// # Examples
+///
+/// ```ignore
+/// use localghost::prelude::*;
+/// use localghost::{dom, html, log};
+///
+/// #[localghost::main]
+/// async fn main() {
+///     let name = "world";
+///     let el = html! {
+///         <div class="greeting">
+///             <p>"Hello, " {name}</p>
+///             <button on:click={|_| log::info!("clicked")}>"Click me"</button>
+///         </div>
+///     };
+///     dom::body().append(el);
+/// }
+/// ```
+#[proc_macro]
+pub fn html(input: TokenStream) -> TokenStream {
+    let html = syn::parse_macro_input!(input as html::Html);
+    html.expand().into()
+}
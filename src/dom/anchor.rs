@@ -0,0 +1,20 @@
+typed_element!(
+    /// An `<a>` element.
+    ///
+    /// [Read more](https://developer.mozilla.org/en-US/docs/Web/HTML/Element/a)
+    Anchor,
+    "a",
+    web_sys::HtmlAnchorElement
+);
+
+impl Anchor {
+    /// Get the URL the anchor links to, resolved against the document.
+    pub fn href(&self) -> String {
+        self.raw().href()
+    }
+
+    /// Set the URL the anchor links to.
+    pub fn set_href(&self, href: &str) {
+        self.raw().set_href(href);
+    }
+}
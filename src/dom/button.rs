@@ -0,0 +1,30 @@
+typed_element!(
+    /// A `<button>` element.
+    ///
+    /// [Read more](https://developer.mozilla.org/en-US/docs/Web/HTML/Element/button)
+    Button,
+    "button",
+    web_sys::HtmlButtonElement
+);
+
+impl Button {
+    /// Get the value submitted with the form when this button is used.
+    pub fn value(&self) -> String {
+        self.raw().value()
+    }
+
+    /// Set the value submitted with the form when this button is used.
+    pub fn set_value(&self, value: &str) {
+        self.raw().set_value(value);
+    }
+
+    /// Returns `true` if the button is disabled.
+    pub fn disabled(&self) -> bool {
+        self.raw().disabled()
+    }
+
+    /// Enable or disable the button.
+    pub fn set_disabled(&self, disabled: bool) {
+        self.raw().set_disabled(disabled);
+    }
+}
@@ -0,0 +1,33 @@
+use crate::dom::{Element, Text};
+
+use std::fmt::Display;
+
+/// A value which can be appended to an [`Element`].
+///
+/// This is used by the [`html!`] macro to append interpolated expressions.
+/// Elements are appended as they are, and any other value is appended as
+/// text.
+///
+/// [`html!`]: crate::html
+pub trait Child {
+    /// Append the value to the end of `parent`.
+    fn append_to(self, parent: &Element);
+}
+
+impl Child for Element {
+    fn append_to(self, parent: &Element) {
+        parent.append(self);
+    }
+}
+
+impl Child for Text {
+    fn append_to(self, parent: &Element) {
+        parent.append(self);
+    }
+}
+
+impl<T: Display> Child for T {
+    fn append_to(self, parent: &Element) {
+        parent.append(Text::new(&self.to_string()));
+    }
+}
@@ -0,0 +1,58 @@
+use crate::prelude::*;
+
+/// The classes of an [`Element`].
+///
+/// This `struct` is created by the [`class_list`] method on [`Element`]. See
+/// its documentation for more.
+///
+/// [Read more](https://developer.mozilla.org/en-US/docs/Web/API/Element/classList)
+///
+/// [`Element`]: crate::dom::Element
+/// [`class_list`]: crate::dom::Element::class_list
+///
+/// # Panics
+///
+/// All methods panic if the class name is empty or contains whitespace.
+#[derive(Debug)]
+pub struct ClassList {
+    inner: web_sys::DomTokenList,
+}
+
+impl ClassList {
+    /// Create a new instance.
+    pub(crate) fn new(inner: web_sys::DomTokenList) -> Self {
+        Self { inner }
+    }
+
+    /// Add a class. Does nothing if the class is already present.
+    pub fn add(&self, class: &str) {
+        self.inner.add_1(class).unwrap_throw();
+    }
+
+    /// Remove a class. Does nothing if the class is not present.
+    pub fn remove(&self, class: &str) {
+        self.inner.remove_1(class).unwrap_throw();
+    }
+
+    /// Remove the class if it's present, and add it otherwise.
+    ///
+    /// Returns `true` if the class is present afterwards.
+    pub fn toggle(&self, class: &str) -> bool {
+        self.inner.toggle(class).unwrap_throw()
+    }
+
+    /// Returns `true` if the class is present.
+    pub fn contains(&self, class: &str) -> bool {
+        self.inner.contains(class)
+    }
+
+    /// Get the number of classes.
+    pub fn len(&self) -> usize {
+        self.inner.length() as usize
+    }
+
+    /// Returns `true` if there are no classes.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
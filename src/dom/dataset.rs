@@ -0,0 +1,75 @@
+use crate::prelude::*;
+
+use std::fmt::Display;
+use std::io;
+use std::str::FromStr;
+
+/// The `data-*` attributes of an [`Element`].
+///
+/// This `struct` is created by the [`dataset`] method on [`Element`]. See its
+/// documentation for more.
+///
+/// Keys are the camelCased attribute names without the `data-` prefix, so the
+/// `data-user-id` attribute is accessed as `userId`.
+///
+/// [Read more](https://developer.mozilla.org/en-US/docs/Web/API/HTMLElement/dataset)
+///
+/// [`Element`]: crate::dom::Element
+/// [`dataset`]: crate::dom::Element::dataset
+#[derive(Debug)]
+pub struct Dataset {
+    inner: web_sys::DomStringMap,
+}
+
+impl Dataset {
+    /// Create a new instance.
+    pub(crate) fn new(inner: web_sys::DomStringMap) -> Self {
+        Self { inner }
+    }
+
+    /// Get the value of an attribute.
+    pub fn get(&self, key: &str) -> Option<String> {
+        self.inner.get(key)
+    }
+
+    /// Get the value of an attribute, and parse it.
+    ///
+    /// # Errors
+    ///
+    /// An error of kind `io::ErrorKind::InvalidData` is returned if the value
+    /// can't be parsed.
+    pub fn parse<T>(&self, key: &str) -> io::Result<Option<T>>
+    where
+        T: FromStr,
+        T::Err: Display,
+    {
+        self.get(key)
+            .map(|value| {
+                value.parse().map_err(|err| {
+                    io::Error::new(
+                        io::ErrorKind::InvalidData,
+                        format!("Dataset: unable to parse `{}`: {}", key, err),
+                    )
+                })
+            })
+            .transpose()
+    }
+
+    /// Set the value of an attribute.
+    ///
+    /// # Panics
+    ///
+    /// This method panics if the key contains a `-` followed by a lowercase
+    /// letter, as it can't be mapped to an attribute name.
+    pub fn set<V>(&self, key: &str, value: V)
+    where
+        V: Display,
+    {
+        self.inner.set(key, &value.to_string()).unwrap_throw();
+    }
+
+    /// Remove an attribute.
+    pub fn remove(&self, key: &str) {
+        self.inner.delete(key);
+    }
+}
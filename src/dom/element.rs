@@ -1,8 +1,11 @@
 use std::borrow::Cow;
+use std::convert::TryFrom;
 
-use crate::dom::Text;
+use crate::dom::{ClassList, Dataset, Elements, Style, Text};
 use crate::prelude::*;
 
+use wasm_bindgen::JsCast;
+
 /// An HTML element.
 #[derive(Debug)]
 pub struct Element {
@@ -50,6 +53,43 @@ impl Element {
         Self { el, kind }
     }
 
+    /// Create a new instance from a `web_sys::Element`, using its tag name as
+    /// the element kind.
+    pub(crate) fn from_element(el: web_sys::Element) -> Self {
+        let kind = el.tag_name();
+        Self {
+            kind: kind.into(),
+            el,
+        }
+    }
+
+    /// Get the kind of element, such as `"div"`.
+    pub fn kind(&self) -> &str {
+        &self.kind
+    }
+
+    /// Downcast into a typed element, such as an [`Input`], or get the
+    /// element back if it's of another kind.
+    ///
+    /// [`Input`]: crate::dom::Input
+    pub fn downcast<T>(self) -> Result<T, Element>
+    where
+        T: TryFrom<Element, Error = Element>,
+    {
+        T::try_from(self)
+    }
+
+    /// Access the raw `web_sys::Element`.
+    pub(crate) fn as_raw(&self) -> &web_sys::Element {
+        &self.el
+    }
+
+    /// Access the element as an `HtmlElement`. SVG and MathML elements share
+    /// its `style` and `dataset` properties.
+    fn html(&self) -> &web_sys::HtmlElement {
+        self.el.unchecked_ref()
+    }
+
     /// Append a child element.
     pub fn append<C>(&self, child: C)
     where
@@ -58,6 +98,106 @@ impl Element {
         self.el.append_child(child.as_ref()).unwrap_throw();
     }
 
+    /// Insert a child element before all other children.
+    pub fn prepend<C>(&self, child: C)
+    where
+        C: AsRef<web_sys::Node>,
+    {
+        self.el.prepend_with_node_1(child.as_ref()).unwrap_throw();
+    }
+
+    /// Insert a sibling directly before this element.
+    ///
+    /// Does nothing if this element has no parent.
+    pub fn before<C>(&self, sibling: C)
+    where
+        C: AsRef<web_sys::Node>,
+    {
+        self.el.before_with_node_1(sibling.as_ref()).unwrap_throw();
+    }
+
+    /// Insert a sibling directly after this element.
+    ///
+    /// Does nothing if this element has no parent.
+    pub fn after<C>(&self, sibling: C)
+    where
+        C: AsRef<web_sys::Node>,
+    {
+        self.el.after_with_node_1(sibling.as_ref()).unwrap_throw();
+    }
+
+    /// Replace this element with another node in its parent.
+    ///
+    /// Does nothing if this element has no parent.
+    pub fn replace_with<C>(&self, other: C)
+    where
+        C: AsRef<web_sys::Node>,
+    {
+        self.el
+            .replace_with_with_node_1(other.as_ref())
+            .unwrap_throw();
+    }
+
+    /// Remove this element from its parent.
+    pub fn remove(&self) {
+        self.el.remove();
+    }
+
+    /// Get the parent element, if there is one.
+    pub fn parent(&self) -> Option<Element> {
+        self.el.parent_element().map(Element::from_element)
+    }
+
+    /// Iterate over the child elements, skipping text and comment nodes.
+    ///
+    /// The children are read as the iterator advances, so changes made while
+    /// iterating are reflected in the output.
+    pub fn children(&self) -> Elements {
+        Elements::new(self.el.child_nodes())
+    }
+
+    /// Get the first child element.
+    pub fn first_child(&self) -> Option<Element> {
+        self.el.first_element_child().map(Element::from_element)
+    }
+
+    /// Get the last child element.
+    pub fn last_child(&self) -> Option<Element> {
+        self.el.last_element_child().map(Element::from_element)
+    }
+
+    /// Get the element directly following this one in its parent.
+    pub fn next_sibling(&self) -> Option<Element> {
+        self.el.next_element_sibling().map(Element::from_element)
+    }
+
+    /// Get the element directly preceding this one in its parent.
+    pub fn previous_sibling(&self) -> Option<Element> {
+        self.el
+            .previous_element_sibling()
+            .map(Element::from_element)
+    }
+
+    /// Create a copy of this element. If `deep` is `true` all of its
+    /// descendants are copied too.
+    ///
+    /// Event listeners are not copied.
+    pub fn clone_node(&self, deep: bool) -> Element {
+        let el = self.el.clone_node_with_deep(deep).unwrap_throw();
+        Self {
+            kind: self.kind.clone(),
+            el: el.unchecked_into(),
+        }
+    }
+
+    /// Returns `true` if `other` is this element, or one of its descendants.
+    pub fn contains<C>(&self, other: C) -> bool
+    where
+        C: AsRef<web_sys::Node>,
+    {
+        self.el.contains(Some(other.as_ref()))
+    }
+
     /// Gets the value of an attribute on the specified element.    pub fn set_attr(&self, name: &str, value: &str) {
     pub fn attr(&self, name: &str) -> Option<String> {
         self.el.get_attribute(name)
@@ -70,12 +210,53 @@ impl Element {
         self.el.set_attribute(name, value).unwrap_throw();
     }
 
+    /// Removes an attribute from the element. Does nothing if the attribute
+    /// isn't set.
+    pub fn remove_attr(&self, name: &str) {
+        self.el.remove_attribute(name).unwrap_throw();
+    }
+
     /// Return the first element that matches the query.
     pub fn query_selector(&self, selectors: &str) -> Option<Element> {
         self.el
             .query_selector(selectors)
             .unwrap_throw()
-            .map(|el| unsafe { Element::from_raw(el.tag_name(), el) })
+            .map(Element::from_element)
+    }
+
+    /// Return all descendant elements that match the query, in document
+    /// order.
+    pub fn query_selector_all(&self, selectors: &str) -> Elements {
+        Elements::new(self.el.query_selector_all(selectors).unwrap_throw())
+    }
+
+    /// Return the closest ancestor that matches the query, starting with the
+    /// element itself.
+    pub fn closest(&self, selectors: &str) -> Option<Element> {
+        self.el
+            .closest(selectors)
+            .unwrap_throw()
+            .map(Element::from_element)
+    }
+
+    /// Returns `true` if the element matches the query.
+    pub fn matches(&self, selectors: &str) -> bool {
+        self.el.matches(selectors).unwrap_throw()
+    }
+
+    /// Access the classes of the element.
+    pub fn class_list(&self) -> ClassList {
+        ClassList::new(self.el.class_list())
+    }
+
+    /// Access the inline style of the element.
+    pub fn style(&self) -> Style {
+        Style::new(self.html().style())
+    }
+
+    /// Access the `data-*` attributes of the element.
+    pub fn dataset(&self) -> Dataset {
+        Dataset::new(self.html().dataset())
     }
 
     /// Get the `textContent` field of this object.
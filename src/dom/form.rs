@@ -0,0 +1,292 @@
+use crate::dom::form_control::Control;
+use crate::dom::{Element, FieldError, FieldErrorKind, FormError, Select};
+use crate::events::{Builder, Event, EventListener, EventTarget};
+use crate::fs::File;
+use crate::prelude::*;
+
+use async_channel::{self as channel, Receiver};
+use futures_core::Stream;
+use serde::de::DeserializeOwned;
+use serde_json::{Map, Number, Value};
+
+use std::pin::Pin;
+use std::rc::Rc;
+use std::task::{Context, Poll};
+
+typed_element!(
+    /// A `<form>` element.
+    ///
+    /// [Read more](https://developer.mozilla.org/en-US/docs/Web/HTML/Element/form)
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use localghost::prelude::*;
+    /// use localghost::dom::{query_selector, Form};
+    /// use localghost::log;
+    /// use async_std::prelude::*;
+    /// use serde::Deserialize;
+    /// use std::convert::TryFrom;
+    ///
+    /// #[derive(Deserialize)]
+    /// struct Login {
+    ///     email: String,
+    ///     remember: bool,
+    /// }
+    ///
+    /// #[localghost::main]
+    /// async fn main() {
+    ///     let form = query_selector("form").unwrap();
+    ///     let form = Form::try_from(form).unwrap();
+    ///
+    ///     let mut submissions = form.submit();
+    ///     while submissions.next().await.is_some() {
+    ///         match form.values::<Login>() {
+    ///             Ok(login) => log::info!("logging in as {}", login.email),
+    ///             Err(err) => log::warn!("{}", err),
+    ///         }
+    ///     }
+    /// }
+    /// ```
+    Form,
+    "form",
+    web_sys::HtmlFormElement
+);
+
+impl Form {
+    /// Get the URL the form is submitted to.
+    pub fn action(&self) -> String {
+        self.raw().action()
+    }
+
+    /// Set the URL the form is submitted to.
+    pub fn set_action(&self, action: &str) {
+        self.raw().set_action(action);
+    }
+
+    /// Restore all controls in the form to their default values.
+    pub fn reset(&self) {
+        self.raw().reset();
+    }
+
+    /// Read the values of all named controls in the form.
+    ///
+    /// Controls which are disabled, and buttons, are skipped. Values are
+    /// read as follows:
+    ///
+    /// - Text fields, text areas and selects are read as a string.
+    /// - Number and range fields are read as a number, or `null` when empty.
+    /// - Checkboxes without a `value` attribute are read as a `bool`, and
+    ///   other checkboxes as a list of the values which are checked.
+    /// - Radio buttons are read as the value which is checked, or `null`.
+    /// - Selects with the `multiple` attribute are read as a list of the
+    ///   selected values.
+    /// - File fields are read as the name of the selected file, or a list of
+    ///   names if they accept multiple files. Use [`files`] to read their
+    ///   contents.
+    ///
+    /// [`files`]: Form::files
+    ///
+    /// # Errors
+    ///
+    /// An error is returned if any of the controls is invalid, or if the
+    /// values can't be deserialized into `T`.
+    pub fn values<T>(&self) -> Result<T, FormError>
+    where
+        T: DeserializeOwned,
+    {
+        self.validate()?;
+
+        let mut values = Map::new();
+        for control in Control::all(self.raw()) {
+            let name = control.name();
+            if name.is_empty() || control.disabled() {
+                continue;
+            }
+            match &control {
+                Control::Input(input) => match input.type_().as_str() {
+                    "submit" | "reset" | "button" | "image" => {}
+                    "checkbox" if !input.has_attribute("value") => {
+                        values.insert(name, Value::Bool(input.checked()));
+                    }
+                    "checkbox" => {
+                        let list = values.entry(name).or_insert_with(|| Value::Array(vec![]));
+                        if let (Value::Array(list), true) = (list, input.checked()) {
+                            list.push(Value::String(input.value()));
+                        }
+                    }
+                    "radio" => {
+                        let value = values.entry(name).or_insert(Value::Null);
+                        if input.checked() {
+                            *value = Value::String(input.value());
+                        }
+                    }
+                    "number" | "range" => {
+                        values.insert(name, number(input.value_as_number()));
+                    }
+                    "file" => {
+                        let el = Element::from_element(input.clone().into());
+                        let mut names = File::from_input(&el).into_iter().map(|f| f.name());
+                        let value = match input.multiple() {
+                            true => Value::Array(names.map(Value::String).collect()),
+                            false => names.next().map_or(Value::Null, Value::String),
+                        };
+                        values.insert(name, value);
+                    }
+                    _ => {
+                        values.insert(name, Value::String(input.value()));
+                    }
+                },
+                Control::Select(select) if select.multiple() => {
+                    let el = Element::from_element(select.clone().into());
+                    let select = el.downcast::<Select>().unwrap_throw();
+                    let selected = select.selected_options().into_iter();
+                    values.insert(name, Value::Array(selected.map(Value::String).collect()));
+                }
+                _ => {
+                    values.insert(name, Value::String(control.value()));
+                }
+            }
+        }
+
+        serde_json::from_value(Value::Object(values)).map_err(FormError::Data)
+    }
+
+    /// Check the constraints of all controls in the form, such as `required`,
+    /// `pattern` and `min`, as well as those set by a [`validator`].
+    ///
+    /// [`validator`]: Form::validator
+    ///
+    /// # Errors
+    ///
+    /// An error listing every invalid control is returned if any of them is
+    /// invalid.
+    pub fn validate(&self) -> Result<(), FormError> {
+        let errors: Vec<_> = Control::all(self.raw())
+            .into_iter()
+            .filter(|control| control.will_validate())
+            .filter_map(|control| {
+                let kind = FieldErrorKind::from_validity(&control.validity())?;
+                Some(FieldError {
+                    name: control.name(),
+                    kind,
+                    message: control.validation_message(),
+                })
+            })
+            .collect();
+        match errors.is_empty() {
+            true => Ok(()),
+            false => Err(FormError::Invalid(errors)),
+        }
+    }
+
+    /// Register a custom validator for the controls with the given name.
+    ///
+    /// The validator is passed the value of the control, and returns a message
+    /// if the value is invalid. It runs right away, and again whenever the
+    /// value changes through user input. Invalid controls are reported by
+    /// [`validate`] and [`values`] with `FieldErrorKind::Custom`, and by the
+    /// browser's own validation UI.
+    ///
+    /// The validator is removed when the returned `EventListener` is dropped.
+    ///
+    /// [`validate`]: Form::validate
+    /// [`values`]: Form::values
+    pub fn validator<F>(&self, name: &str, f: F) -> EventListener
+    where
+        F: Fn(&str) -> Option<String> + 'static,
+    {
+        let check = Rc::new(move |control: &Control| {
+            let message = f(&control.value());
+            control.set_custom_validity(message.as_deref().unwrap_or(""));
+        });
+        for control in Control::all(self.raw()) {
+            if control.name() == name {
+                check(&control);
+            }
+        }
+
+        let name = name.to_owned();
+        self.on_with("input", move |ev| {
+            let control = ev
+                .target::<web_sys::Element>()
+                .and_then(Control::new)
+                .filter(|control| control.name() == name);
+            if let Some(control) = control {
+                check(&control);
+            }
+        })
+    }
+
+    /// Get the files selected in the file controls with the given name.
+    pub fn files(&self, name: &str) -> Vec<File> {
+        Control::all(self.raw())
+            .into_iter()
+            .filter(|control| control.name() == name)
+            .filter_map(|control| match control {
+                Control::Input(input) => Some(Element::from_element(input.into())),
+                _ => None,
+            })
+            .flat_map(|el| File::from_input(&el))
+            .collect()
+    }
+
+    /// Create a stream of `submit` events.
+    ///
+    /// The browser's default action, navigating to the form's `action`, is
+    /// prevented for as long as the stream exists. The browser only submits
+    /// the form once all its constraints are met, so [`values`] is expected
+    /// to succeed unless the form has the `novalidate` attribute, or it's
+    /// submitted through a button with the `formnovalidate` attribute.
+    ///
+    /// [`values`]: Form::values
+    pub fn submit(&self) -> Submissions {
+        let (sender, receiver) = channel::unbounded();
+        let listener = Builder::new()
+            .passive(false)
+            .listen(self, "submit", move |ev: Event| {
+                let raw: &web_sys::Event = ev.as_ref();
+                raw.prevent_default();
+                let _ = sender.try_send(ev);
+            });
+        Submissions {
+            _listener: listener,
+            receiver,
+        }
+    }
+}
+
+/// Convert the value of a number field to JSON, using an integer if possible.
+fn number(value: f64) -> Value {
+    if value.is_nan() {
+        Value::Null
+    } else if value.fract() == 0.0 && value.abs() < i64::MAX as f64 {
+        Value::Number((value as i64).into())
+    } else {
+        Number::from_f64(value).map_or(Value::Null, Value::Number)
+    }
+}
+
+/// A stream of submissions of a [`Form`].
+///
+/// This `struct` is created by the [`submit`] method on [`Form`]. See its
+/// documentation for more.
+///
+/// [`submit`]: Form::submit
+#[pin_project::pin_project]
+#[derive(Debug)]
+#[must_use = "Streams do nothing unless polled"]
+pub struct Submissions {
+    _listener: EventListener,
+    #[pin]
+    receiver: Receiver<Event>,
+}
+
+impl Stream for Submissions {
+    type Item = Event;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = self.project();
+        this.receiver.poll_next(cx)
+    }
+}
@@ -0,0 +1,95 @@
+use crate::prelude::*;
+
+use wasm_bindgen::JsCast;
+use web_sys::{HtmlInputElement, HtmlSelectElement, HtmlTextAreaElement};
+
+/// A control in a form which holds a value, and takes part in validation.
+pub(crate) enum Control {
+    Input(HtmlInputElement),
+    Select(HtmlSelectElement),
+    TextArea(HtmlTextAreaElement),
+}
+
+impl Control {
+    /// Get the control for an element, if it is one.
+    pub(crate) fn new(el: web_sys::Element) -> Option<Self> {
+        let el = match el.dyn_into::<HtmlInputElement>() {
+            Ok(el) => return Some(Control::Input(el)),
+            Err(el) => el,
+        };
+        let el = match el.dyn_into::<HtmlSelectElement>() {
+            Ok(el) => return Some(Control::Select(el)),
+            Err(el) => el,
+        };
+        el.dyn_into::<HtmlTextAreaElement>()
+            .ok()
+            .map(Control::TextArea)
+    }
+
+    /// Get all controls in a form, in document order.
+    pub(crate) fn all(form: &web_sys::HtmlFormElement) -> Vec<Self> {
+        let elements = form.elements();
+        (0..elements.length())
+            .filter_map(|i| elements.item(i))
+            .filter_map(Control::new)
+            .collect()
+    }
+
+    pub(crate) fn name(&self) -> String {
+        match self {
+            Control::Input(el) => el.name(),
+            Control::Select(el) => el.name(),
+            Control::TextArea(el) => el.name(),
+        }
+    }
+
+    pub(crate) fn value(&self) -> String {
+        match self {
+            Control::Input(el) => el.value(),
+            Control::Select(el) => el.value(),
+            Control::TextArea(el) => el.value(),
+        }
+    }
+
+    pub(crate) fn disabled(&self) -> bool {
+        match self {
+            Control::Input(el) => el.disabled(),
+            Control::Select(el) => el.disabled(),
+            Control::TextArea(el) => el.disabled(),
+        }
+    }
+
+    /// Returns `true` if the control is subject to constraint validation.
+    pub(crate) fn will_validate(&self) -> bool {
+        match self {
+            Control::Input(el) => el.will_validate(),
+            Control::Select(el) => el.will_validate(),
+            Control::TextArea(el) => el.will_validate(),
+        }
+    }
+
+    pub(crate) fn validity(&self) -> web_sys::ValidityState {
+        match self {
+            Control::Input(el) => el.validity(),
+            Control::Select(el) => el.validity(),
+            Control::TextArea(el) => el.validity(),
+        }
+    }
+
+    pub(crate) fn validation_message(&self) -> String {
+        let message = match self {
+            Control::Input(el) => el.validation_message(),
+            Control::Select(el) => el.validation_message(),
+            Control::TextArea(el) => el.validation_message(),
+        };
+        message.unwrap_throw()
+    }
+
+    pub(crate) fn set_custom_validity(&self, message: &str) {
+        match self {
+            Control::Input(el) => el.set_custom_validity(message),
+            Control::Select(el) => el.set_custom_validity(message),
+            Control::TextArea(el) => el.set_custom_validity(message),
+        }
+    }
+}
@@ -0,0 +1,144 @@
+use std::error::Error;
+use std::fmt::{self, Display};
+use std::io;
+
+/// Why the value of a form field is invalid.
+///
+/// [Read more](https://developer.mozilla.org/en-US/docs/Web/API/ValidityState)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FieldErrorKind {
+    /// A required field has no value.
+    ValueMissing,
+    /// The value doesn't match the field type, such as `email` or `url`.
+    TypeMismatch,
+    /// The value doesn't match the `pattern` attribute.
+    PatternMismatch,
+    /// The value is longer than the `maxlength` attribute.
+    TooLong,
+    /// The value is shorter than the `minlength` attribute.
+    TooShort,
+    /// The value is less than the `min` attribute.
+    RangeUnderflow,
+    /// The value is greater than the `max` attribute.
+    RangeOverflow,
+    /// The value doesn't match the `step` attribute.
+    StepMismatch,
+    /// The browser is unable to convert the user's input.
+    BadInput,
+    /// The value was rejected by a custom validator.
+    Custom,
+}
+
+impl FieldErrorKind {
+    /// Get the kind of error from a `ValidityState`, if the field is invalid.
+    pub(crate) fn from_validity(validity: &web_sys::ValidityState) -> Option<Self> {
+        let kind = if validity.value_missing() {
+            FieldErrorKind::ValueMissing
+        } else if validity.type_mismatch() {
+            FieldErrorKind::TypeMismatch
+        } else if validity.pattern_mismatch() {
+            FieldErrorKind::PatternMismatch
+        } else if validity.too_long() {
+            FieldErrorKind::TooLong
+        } else if validity.too_short() {
+            FieldErrorKind::TooShort
+        } else if validity.range_underflow() {
+            FieldErrorKind::RangeUnderflow
+        } else if validity.range_overflow() {
+            FieldErrorKind::RangeOverflow
+        } else if validity.step_mismatch() {
+            FieldErrorKind::StepMismatch
+        } else if validity.bad_input() {
+            FieldErrorKind::BadInput
+        } else if validity.custom_error() {
+            FieldErrorKind::Custom
+        } else {
+            return None;
+        };
+        Some(kind)
+    }
+}
+
+/// An invalid form field.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FieldError {
+    pub(crate) name: String,
+    pub(crate) kind: FieldErrorKind,
+    pub(crate) message: String,
+}
+
+impl FieldError {
+    /// Get the name of the field.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Get why the field is invalid.
+    pub fn kind(&self) -> FieldErrorKind {
+        self.kind
+    }
+
+    /// Get the message describing the error, as shown by the browser.
+    pub fn message(&self) -> &str {
+        &self.message
+    }
+}
+
+impl Display for FieldError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}: {}", self.name, self.message)
+    }
+}
+
+/// An error returned when reading a form.
+#[derive(Debug)]
+pub enum FormError {
+    /// One or more fields are invalid.
+    Invalid(Vec<FieldError>),
+    /// The values couldn't be deserialized into the requested type.
+    Data(serde_json::Error),
+}
+
+impl FormError {
+    /// Get the errors of the invalid fields, if there are any.
+    pub fn fields(&self) -> &[FieldError] {
+        match self {
+            FormError::Invalid(fields) => fields,
+            FormError::Data(_) => &[],
+        }
+    }
+}
+
+impl Display for FormError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            FormError::Invalid(fields) => {
+                write!(f, "Form: invalid fields:")?;
+                for field in fields {
+                    write!(f, " {};", field)?;
+                }
+                Ok(())
+            }
+            FormError::Data(err) => write!(f, "Form: {}", err),
+        }
+    }
+}
+
+impl Error for FormError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            FormError::Invalid(_) => None,
+            FormError::Data(err) => Some(err),
+        }
+    }
+}
+
+impl From<FormError> for io::Error {
+    fn from(err: FormError) -> Self {
+        let kind = match err {
+            FormError::Invalid(_) => io::ErrorKind::InvalidInput,
+            FormError::Data(_) => io::ErrorKind::InvalidData,
+        };
+        io::Error::new(kind, err)
+    }
+}
@@ -0,0 +1,30 @@
+typed_element!(
+    /// An `<img>` element.
+    ///
+    /// [Read more](https://developer.mozilla.org/en-US/docs/Web/HTML/Element/img)
+    Image,
+    "img",
+    web_sys::HtmlImageElement
+);
+
+impl Image {
+    /// Get the URL of the image, resolved against the document.
+    pub fn src(&self) -> String {
+        self.raw().src()
+    }
+
+    /// Set the URL of the image.
+    pub fn set_src(&self, src: &str) {
+        self.raw().set_src(src);
+    }
+
+    /// Get the text shown when the image can't be displayed.
+    pub fn alt(&self) -> String {
+        self.raw().alt()
+    }
+
+    /// Set the text shown when the image can't be displayed.
+    pub fn set_alt(&self, alt: &str) {
+        self.raw().set_alt(alt);
+    }
+}
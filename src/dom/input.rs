@@ -0,0 +1,40 @@
+typed_element!(
+    /// An `<input>` element.
+    ///
+    /// [Read more](https://developer.mozilla.org/en-US/docs/Web/HTML/Element/input)
+    Input,
+    "input",
+    web_sys::HtmlInputElement
+);
+
+impl Input {
+    /// Get the current value of the input.
+    pub fn value(&self) -> String {
+        self.raw().value()
+    }
+
+    /// Set the current value of the input.
+    pub fn set_value(&self, value: &str) {
+        self.raw().set_value(value);
+    }
+
+    /// Returns `true` if the checkbox or radio button is checked.
+    pub fn checked(&self) -> bool {
+        self.raw().checked()
+    }
+
+    /// Check or uncheck the checkbox or radio button.
+    pub fn set_checked(&self, checked: bool) {
+        self.raw().set_checked(checked);
+    }
+
+    /// Returns `true` if the input is disabled.
+    pub fn disabled(&self) -> bool {
+        self.raw().disabled()
+    }
+
+    /// Enable or disable the input.
+    pub fn set_disabled(&self, disabled: bool) {
+        self.raw().set_disabled(disabled);
+    }
+}
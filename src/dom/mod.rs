@@ -14,14 +14,51 @@
 //! ```
 
 // re-exports, temporary only
+pub use anchor::Anchor;
+pub use button::Button;
+pub use child::Child;
+pub use class_list::ClassList;
+pub use dataset::Dataset;
 pub use element::Element;
-pub use query_selector::query_selector;
+pub use form::{Form, Submissions};
+pub use form_error::{FieldError, FieldErrorKind, FormError};
+pub use image::Image;
+pub use input::Input;
+pub use mutations::{Mutation, Mutations, MutationsBuilder};
+pub use query_selector::{query_selector, query_selector_all, Elements};
+pub use select::Select;
+pub use style::Style;
 pub use text::Text;
+pub use text_area::TextArea;
+pub(crate) use vdom::VChildren;
+pub use vdom::VDom;
+pub use vnode::{VElement, VNode};
+pub use wait_for_selector::{wait_for_selector, wait_for_selector_removed};
 pub use window::Window;
 
+#[macro_use]
+mod typed_element;
+
+mod anchor;
+mod button;
+mod child;
+mod class_list;
+mod dataset;
 mod element;
+mod form;
+mod form_control;
+mod form_error;
+mod image;
+mod input;
+mod mutations;
 mod query_selector;
+mod select;
+mod style;
 mod text;
+mod text_area;
+mod vdom;
+mod vnode;
+mod wait_for_selector;
 mod window;
 
 use crate::prelude::*;
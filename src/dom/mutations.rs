@@ -0,0 +1,277 @@
+use crate::dom::Element;
+use crate::utils::ResultExt;
+
+use async_channel::{self as channel, Receiver};
+use futures_core::Stream;
+use js_sys::Array;
+use wasm_bindgen::prelude::*;
+use wasm_bindgen::JsCast;
+use web_sys::{MutationObserver, MutationObserverInit, MutationRecord, Node};
+
+use std::fmt::{self, Debug};
+use std::io;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+
+/// A change to the DOM, observed by [`Mutations`].
+///
+/// [Read more](https://developer.mozilla.org/en-US/docs/Web/API/MutationRecord)
+#[derive(Debug)]
+pub enum Mutation {
+    /// Child nodes were added to or removed from `target`.
+    ChildList {
+        /// The node whose children changed.
+        target: Node,
+        /// The nodes which were added.
+        added: Vec<Node>,
+        /// The nodes which were removed.
+        removed: Vec<Node>,
+    },
+    /// An attribute of `target` was set or removed.
+    Attribute {
+        /// The element whose attribute changed.
+        target: Element,
+        /// The name of the attribute.
+        name: String,
+        /// The previous value of the attribute, if old values are recorded
+        /// and the attribute was set.
+        old_value: Option<String>,
+    },
+    /// The text of `target` changed.
+    CharacterData {
+        /// The text or comment node which changed.
+        target: Node,
+        /// The previous text, if old values are recorded.
+        old_value: Option<String>,
+    },
+}
+
+impl Mutation {
+    fn from_record(record: MutationRecord) -> Option<Self> {
+        let target = record.target()?;
+        let mutation = match record.type_().as_str() {
+            "childList" => Mutation::ChildList {
+                target,
+                added: nodes(record.added_nodes()),
+                removed: nodes(record.removed_nodes()),
+            },
+            "attributes" => Mutation::Attribute {
+                target: Element::from_element(target.dyn_into().ok()?),
+                name: record.attribute_name()?,
+                old_value: record.old_value(),
+            },
+            "characterData" => Mutation::CharacterData {
+                target,
+                old_value: record.old_value(),
+            },
+            _ => return None,
+        };
+        Some(mutation)
+    }
+
+    /// Get the node which changed.
+    pub fn target(&self) -> &Node {
+        match self {
+            Mutation::ChildList { target, .. } => target,
+            Mutation::Attribute { target, .. } => target.as_ref(),
+            Mutation::CharacterData { target, .. } => target,
+        }
+    }
+}
+
+fn nodes(list: web_sys::NodeList) -> Vec<Node> {
+    (0..list.length()).filter_map(|i| list.item(i)).collect()
+}
+
+/// A stream of changes to a DOM tree.
+///
+/// The observer is disconnected when the stream is dropped.
+///
+/// [Read more](https://developer.mozilla.org/en-US/docs/Web/API/MutationObserver)
+///
+/// # Examples
+///
+/// ```no_run
+/// use localghost::prelude::*;
+/// use localghost::dom::{body, Mutation, Mutations};
+/// use localghost::log;
+/// use async_std::prelude::*;
+/// use std::io;
+///
+/// #[localghost::main]
+/// async fn main() -> io::Result<()> {
+///     let mut mutations = Mutations::builder()
+///         .child_list(true)
+///         .subtree(true)
+///         .observe(&body())?;
+///
+///     while let Some(mutation) = mutations.next().await {
+///         if let Mutation::ChildList { added, .. } = mutation {
+///             log::info!("{} nodes added", added.len());
+///         }
+///     }
+///     Ok(())
+/// }
+/// ```
+#[pin_project::pin_project]
+#[derive(Debug)]
+#[must_use = "Streams do nothing unless polled"]
+pub struct Mutations {
+    observer: Observer,
+    #[pin]
+    receiver: Receiver<Mutation>,
+}
+
+impl Mutations {
+    /// Create a new builder to configure which changes are observed.
+    pub fn builder() -> MutationsBuilder {
+        MutationsBuilder {
+            child_list: false,
+            attributes: false,
+            attribute_filter: None,
+            character_data: false,
+            old_value: false,
+            subtree: false,
+        }
+    }
+}
+
+impl Stream for Mutations {
+    type Item = Mutation;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = self.project();
+        this.receiver.poll_next(cx)
+    }
+}
+
+/// A `MutationObserver` which is disconnected when dropped.
+struct Observer {
+    observer: MutationObserver,
+    _callback: Closure<dyn FnMut(Array)>,
+}
+
+impl Drop for Observer {
+    fn drop(&mut self) {
+        self.observer.disconnect();
+    }
+}
+
+impl Debug for Observer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Observer")
+            .field("observer", &self.observer)
+            .finish()
+    }
+}
+
+/// Configure which changes [`Mutations`] observes.
+///
+/// This `struct` is created by the [`builder`] method on [`Mutations`]. See
+/// its documentation for more.
+///
+/// [`builder`]: Mutations::builder
+#[derive(Debug)]
+pub struct MutationsBuilder {
+    child_list: bool,
+    attributes: bool,
+    attribute_filter: Option<Vec<String>>,
+    character_data: bool,
+    old_value: bool,
+    subtree: bool,
+}
+
+impl MutationsBuilder {
+    /// Observe child nodes being added and removed.
+    pub fn child_list(mut self, child_list: bool) -> Self {
+        self.child_list = child_list;
+        self
+    }
+
+    /// Observe attributes being set and removed.
+    pub fn attributes(mut self, attributes: bool) -> Self {
+        self.attributes = attributes;
+        self
+    }
+
+    /// Only observe the attributes with the given names. Implies
+    /// `attributes(true)`.
+    pub fn attribute_filter<I, S>(mut self, names: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.attributes = true;
+        self.attribute_filter = Some(names.into_iter().map(Into::into).collect());
+        self
+    }
+
+    /// Observe the text of text and comment nodes changing.
+    pub fn character_data(mut self, character_data: bool) -> Self {
+        self.character_data = character_data;
+        self
+    }
+
+    /// Record the previous value of changed attributes and text.
+    pub fn old_value(mut self, old_value: bool) -> Self {
+        self.old_value = old_value;
+        self
+    }
+
+    /// Observe all descendants of the target, rather than only the target
+    /// itself.
+    pub fn subtree(mut self, subtree: bool) -> Self {
+        self.subtree = subtree;
+        self
+    }
+
+    /// Start observing `target`.
+    ///
+    /// # Errors
+    ///
+    /// An error of kind `io::ErrorKind::InvalidInput` is returned if none of
+    /// `child_list`, `attributes` and `character_data` was set.
+    pub fn observe<T>(self, target: T) -> io::Result<Mutations>
+    where
+        T: AsRef<Node>,
+    {
+        let options = MutationObserverInit::new();
+        options.set_child_list(self.child_list);
+        options.set_attributes(self.attributes);
+        options.set_character_data(self.character_data);
+        options.set_subtree(self.subtree);
+        if self.attributes {
+            options.set_attribute_old_value(self.old_value);
+        }
+        if self.character_data {
+            options.set_character_data_old_value(self.old_value);
+        }
+        if let Some(filter) = &self.attribute_filter {
+            let filter: Array = filter.iter().map(|name| JsValue::from_str(name)).collect();
+            options.set_attribute_filter(&filter);
+        }
+
+        let (sender, receiver) = channel::unbounded();
+        let callback = Closure::wrap(Box::new(move |records: Array| {
+            let mutations = records
+                .iter()
+                .filter_map(|record| Mutation::from_record(record.unchecked_into()));
+            for mutation in mutations {
+                let _ = sender.try_send(mutation);
+            }
+        }) as Box<dyn FnMut(Array)>);
+        let observer = MutationObserver::new(callback.as_ref().unchecked_ref())
+            .err_kind(io::ErrorKind::Other)?;
+        observer
+            .observe_with_options(target.as_ref(), &options)
+            .err_kind(io::ErrorKind::InvalidInput)?;
+
+        Ok(Mutations {
+            observer: Observer {
+                observer,
+                _callback: callback,
+            },
+            receiver,
+        })
+    }
+}
@@ -1,27 +1,73 @@
 use crate::dom::Element;
 use crate::prelude::*;
 
+use wasm_bindgen::JsCast;
+
 /// Return the first element that matches the query.
 pub fn query_selector(selectors: &str) -> Option<Element> {
     crate::utils::document()
         .query_selector(selectors)
         .unwrap_throw()
-        .map(|el| unsafe { Element::from_raw(el.tag_name(), el) })
+        .map(Element::from_element)
+}
+
+/// Return all elements that match the query, in document order.
+///
+/// # Examples
+///
+/// ```no_run
+/// use localghost::prelude::*;
+/// use localghost::{dom, log};
+///
+/// #[localghost::main]
+/// async fn main() {
+///     for el in dom::query_selector_all("li.done") {
+///         log::info!("done: {:?}", el.text());
+///     }
+/// }
+/// ```
+pub fn query_selector_all(selectors: &str) -> Elements {
+    let list = crate::utils::document()
+        .query_selector_all(selectors)
+        .unwrap_throw();
+    Elements::new(list)
+}
+
+/// An iterator over the elements matching a query.
+///
+/// This `struct` is created by the [`query_selector_all`] function, and the
+/// [`Element::query_selector_all`] method. See their documentation for more.
+#[derive(Debug)]
+pub struct Elements {
+    list: web_sys::NodeList,
+    index: u32,
 }
 
-// TODO: A `Node` is not an `Element`. We need to cast each `Node` into an
-// `Element` using runtime reflection. Maybe something for Ira?
-// /// Return the first element that matches the query.
-// pub fn query_selector_all(selectors: &str) -> Vec<Element> {
-//     let list = crate::utils::document()
-//         .query_selector_all(selectors)
-//         .unwrap_throw();
-
-//     let mut out = vec![];
-//     for i in 0..list.length() {
-//         let el = list.get(i).unwrap_throw();
-//         let kind = ElementKind::from_str(&el.node_name()).unwrap_throw();
-//         out.push(unsafe { Element::from_raw(kind, el) });
-//     }
-//     out
-// }
+impl Elements {
+    /// Create a new instance.
+    pub(crate) fn new(list: web_sys::NodeList) -> Self {
+        Self { list, index: 0 }
+    }
+}
+
+impl Iterator for Elements {
+    type Item = Element;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while self.index < self.list.length() {
+            let node = self.list.get(self.index).unwrap_throw();
+            self.index += 1;
+            // A `NodeList` can hold any kind of node, so skip everything which
+            // isn't an element.
+            if let Ok(el) = node.dyn_into::<web_sys::Element>() {
+                return Some(Element::from_element(el));
+            }
+        }
+        None
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let remaining = self.list.length().saturating_sub(self.index);
+        (0, Some(remaining as usize))
+    }
+}
@@ -0,0 +1,47 @@
+use crate::prelude::*;
+
+use wasm_bindgen::JsCast;
+
+typed_element!(
+    /// A `<select>` element.
+    ///
+    /// [Read more](https://developer.mozilla.org/en-US/docs/Web/HTML/Element/select)
+    Select,
+    "select",
+    web_sys::HtmlSelectElement
+);
+
+impl Select {
+    /// Get the value of the first selected option, or an empty string if
+    /// none is selected.
+    pub fn value(&self) -> String {
+        self.raw().value()
+    }
+
+    /// Select the first option with the given value.
+    pub fn set_value(&self, value: &str) {
+        self.raw().set_value(value);
+    }
+
+    /// Get the values of all selected options.
+    pub fn selected_options(&self) -> Vec<String> {
+        let options = self.raw().selected_options();
+        (0..options.length())
+            .filter_map(|i| options.item(i))
+            .map(|option| {
+                let option: web_sys::HtmlOptionElement = option.dyn_into().unwrap_throw();
+                option.value()
+            })
+            .collect()
+    }
+
+    /// Returns `true` if the select is disabled.
+    pub fn disabled(&self) -> bool {
+        self.raw().disabled()
+    }
+
+    /// Enable or disable the select.
+    pub fn set_disabled(&self, disabled: bool) {
+        self.raw().set_disabled(disabled);
+    }
+}
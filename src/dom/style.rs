@@ -0,0 +1,46 @@
+use crate::prelude::*;
+
+/// The inline style of an [`Element`].
+///
+/// This `struct` is created by the [`style`] method on [`Element`]. See its
+/// documentation for more.
+///
+/// Property names use CSS syntax, such as `background-color`. Custom
+/// properties such as `--accent` are supported too.
+///
+/// [Read more](https://developer.mozilla.org/en-US/docs/Web/API/CSSStyleDeclaration)
+///
+/// [`Element`]: crate::dom::Element
+/// [`style`]: crate::dom::Element::style
+#[derive(Debug)]
+pub struct Style {
+    inner: web_sys::CssStyleDeclaration,
+}
+
+impl Style {
+    /// Create a new instance.
+    pub(crate) fn new(inner: web_sys::CssStyleDeclaration) -> Self {
+        Self { inner }
+    }
+
+    /// Get the value of a property, or `None` if it isn't set.
+    pub fn get(&self, name: &str) -> Option<String> {
+        let value = self.inner.get_property_value(name).unwrap_throw();
+        match value.is_empty() {
+            true => None,
+            false => Some(value),
+        }
+    }
+
+    /// Set the value of a property.
+    ///
+    /// Values the browser can't parse are ignored.
+    pub fn set(&self, name: &str, value: &str) {
+        self.inner.set_property(name, value).unwrap_throw();
+    }
+
+    /// Remove a property.
+    pub fn remove(&self, name: &str) {
+        self.inner.remove_property(name).unwrap_throw();
+    }
+}
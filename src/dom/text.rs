@@ -10,6 +10,16 @@ impl Text {
         let inner = crate::utils::document().create_text_node(text);
         Self { inner }
     }
+
+    /// Get the contents of the text node.
+    pub fn text(&self) -> String {
+        self.inner.data()
+    }
+
+    /// Set the contents of the text node.
+    pub fn set_text(&self, text: &str) {
+        self.inner.set_data(text);
+    }
 }
 
 impl AsRef<web_sys::Node> for Text {
@@ -0,0 +1,30 @@
+typed_element!(
+    /// A `<textarea>` element.
+    ///
+    /// [Read more](https://developer.mozilla.org/en-US/docs/Web/HTML/Element/textarea)
+    TextArea,
+    "textarea",
+    web_sys::HtmlTextAreaElement
+);
+
+impl TextArea {
+    /// Get the current value of the text area.
+    pub fn value(&self) -> String {
+        self.raw().value()
+    }
+
+    /// Set the current value of the text area.
+    pub fn set_value(&self, value: &str) {
+        self.raw().set_value(value);
+    }
+
+    /// Returns `true` if the text area is disabled.
+    pub fn disabled(&self) -> bool {
+        self.raw().disabled()
+    }
+
+    /// Enable or disable the text area.
+    pub fn set_disabled(&self, disabled: bool) {
+        self.raw().set_disabled(disabled);
+    }
+}
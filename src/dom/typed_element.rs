@@ -0,0 +1,87 @@
+/// Define a wrapper around an [`Element`] of a known kind.
+///
+/// The wrapper can be created from an `Element` through `TryFrom`, which
+/// checks both the element kind and the type of the underlying JS object.
+///
+/// [`Element`]: crate::dom::Element
+macro_rules! typed_element {
+    ($(#[$attr:meta])* $name:ident, $kind:literal, $raw:ty) => {
+        $(#[$attr])*
+        #[derive(Debug)]
+        pub struct $name {
+            el: crate::dom::Element,
+        }
+
+        impl $name {
+            /// Create a new instance.
+            pub fn new() -> Self {
+                Self {
+                    el: crate::dom::Element::new($kind),
+                }
+            }
+
+            /// Access the raw element.
+            fn raw(&self) -> &$raw {
+                wasm_bindgen::JsCast::unchecked_ref(self.el.as_raw())
+            }
+        }
+
+        impl Default for $name {
+            fn default() -> Self {
+                Self::new()
+            }
+        }
+
+        impl std::convert::TryFrom<crate::dom::Element> for $name {
+            type Error = crate::dom::Element;
+
+            /// Downcast an `Element`, or return it if it's of another kind.
+            fn try_from(el: crate::dom::Element) -> Result<Self, Self::Error> {
+                let is_kind = el.kind().eq_ignore_ascii_case($kind)
+                    && wasm_bindgen::JsCast::has_type::<$raw>(el.as_raw());
+                match is_kind {
+                    true => Ok(Self { el }),
+                    false => Err(el),
+                }
+            }
+        }
+
+        impl From<$name> for crate::dom::Element {
+            fn from(el: $name) -> Self {
+                el.el
+            }
+        }
+
+        impl crate::dom::Child for $name {
+            fn append_to(self, parent: &crate::dom::Element) {
+                parent.append(self);
+            }
+        }
+
+        impl std::ops::Deref for $name {
+            type Target = crate::dom::Element;
+
+            fn deref(&self) -> &Self::Target {
+                &self.el
+            }
+        }
+
+        impl AsRef<crate::dom::Element> for $name {
+            fn as_ref(&self) -> &crate::dom::Element {
+                &self.el
+            }
+        }
+
+        impl AsRef<web_sys::Node> for $name {
+            fn as_ref(&self) -> &web_sys::Node {
+                self.el.as_ref()
+            }
+        }
+
+        impl AsRef<web_sys::EventTarget> for $name {
+            fn as_ref(&self) -> &web_sys::EventTarget {
+                self.el.as_ref()
+            }
+        }
+    };
+}
@@ -0,0 +1,321 @@
+use crate::dom::{Element, Text, VElement, VNode};
+use crate::events::{Event, EventListener};
+use crate::prelude::*;
+
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::fmt::{self, Debug};
+use std::mem;
+use std::rc::Rc;
+
+/// A tree of DOM nodes which is kept in sync with a [`VNode`].
+///
+/// Every call to [`render`] compares the new virtual tree against the previous
+/// one, and only updates the attributes, text, listeners and nodes which
+/// changed. Nodes which are kept keep their state, such as focus and
+/// selection. The rendered nodes are removed when the `VDom` is dropped.
+///
+/// [`render`]: VDom::render
+///
+/// # Examples
+///
+/// ```no_run
+/// use localghost::prelude::*;
+/// use localghost::dom::{body, VDom, VElement};
+///
+/// #[localghost::main]
+/// async fn main() {
+///     let mut vdom = VDom::new(body());
+///     for count in 0..3 {
+///         let text = format!("count: {}", count);
+///         vdom.render(VElement::new("p").text(&text));
+///     }
+/// }
+/// ```
+pub struct VDom {
+    parent: web_sys::Node,
+    root: Option<Live>,
+}
+
+impl VDom {
+    /// Create a new instance which renders into the end of `parent`.
+    pub fn new<P>(parent: P) -> Self
+    where
+        P: AsRef<web_sys::Node>,
+    {
+        Self {
+            parent: parent.as_ref().clone(),
+            root: None,
+        }
+    }
+
+    /// Update the DOM to match `node`.
+    pub fn render<N>(&mut self, node: N)
+    where
+        N: Into<VNode>,
+    {
+        let node = node.into();
+        let root = match &mut self.root {
+            Some(root) => root,
+            None => {
+                let root = Live::create(node);
+                self.parent.append_child(root.node()).unwrap_throw();
+                self.root = Some(root);
+                return;
+            }
+        };
+        if let Err(node) = root.patch(node) {
+            let new = Live::create(node);
+            self.parent
+                .replace_child(new.node(), root.node())
+                .unwrap_throw();
+            *root = new;
+        }
+    }
+}
+
+impl Drop for VDom {
+    fn drop(&mut self) {
+        if let Some(root) = &self.root {
+            root.remove();
+        }
+    }
+}
+
+impl Debug for VDom {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("VDom")
+            .field("parent", &self.parent)
+            .field("mounted", &self.root.is_some())
+            .finish()
+    }
+}
+
+/// The children of an element which are kept in sync with a list of
+/// [`VNode`]s.
+#[derive(Default)]
+pub(crate) struct VChildren {
+    children: Vec<Live>,
+}
+
+impl VChildren {
+    /// Update the children of `parent` to match `nodes`. Any other children
+    /// of `parent` are removed.
+    pub(crate) fn render(&mut self, parent: &Element, nodes: Vec<VNode>) {
+        patch_children(parent, &mut self.children, nodes);
+        let parent: &web_sys::Node = parent.as_ref();
+        let nodes = parent.child_nodes();
+        for i in (self.children.len() as u32..nodes.length()).rev() {
+            if let Some(node) = nodes.item(i) {
+                parent.remove_child(&node).unwrap_throw();
+            }
+        }
+    }
+}
+
+/// An event handler which can be swapped while its listener stays registered.
+type Handler = Rc<RefCell<Option<Box<dyn FnMut(Event)>>>>;
+
+/// A rendered node, and the virtual node it was rendered from.
+enum Live {
+    Element(LiveElement),
+    Text(Text),
+}
+
+struct LiveElement {
+    el: Element,
+    key: Option<String>,
+    attrs: Vec<(String, String)>,
+    listeners: Vec<LiveListener>,
+    children: Vec<Live>,
+}
+
+struct LiveListener {
+    event_type: String,
+    handler: Handler,
+    _listener: EventListener,
+}
+
+impl Live {
+    /// Create the DOM nodes for a virtual node.
+    fn create(node: VNode) -> Self {
+        match node {
+            VNode::Text(text) => Live::Text(Text::new(&text)),
+            VNode::Element(vel) => {
+                let el = Element::new(vel.kind);
+                for (name, value) in &vel.attrs {
+                    el.set_attr(name, value);
+                }
+                let listeners = vel
+                    .listeners
+                    .into_iter()
+                    .map(|(event_type, f)| LiveListener::new(&el, event_type, f))
+                    .collect();
+                let children: Vec<_> = vel.children.into_iter().map(Live::create).collect();
+                for child in &children {
+                    el.append(child.node());
+                }
+                Live::Element(LiveElement {
+                    el,
+                    key: vel.key,
+                    attrs: vel.attrs,
+                    listeners,
+                    children,
+                })
+            }
+        }
+    }
+
+    fn node(&self) -> &web_sys::Node {
+        match self {
+            Live::Element(live) => live.el.as_ref(),
+            Live::Text(text) => text.as_ref(),
+        }
+    }
+
+    fn key(&self) -> Option<&str> {
+        match self {
+            Live::Element(live) => live.key.as_deref(),
+            Live::Text(_) => None,
+        }
+    }
+
+    /// Remove the node from its parent.
+    fn remove(&self) {
+        let node = self.node();
+        if let Some(parent) = node.parent_node() {
+            let _ = parent.remove_child(node);
+        }
+    }
+
+    /// Update the node in place to match `node`, or return `node` if it has
+    /// to be replaced instead.
+    fn patch(&mut self, node: VNode) -> Result<(), VNode> {
+        match (self, node) {
+            (Live::Text(text), VNode::Text(new)) => {
+                if text.text() != new {
+                    text.set_text(&new);
+                }
+                Ok(())
+            }
+            (Live::Element(live), VNode::Element(vel))
+                if live.el.kind() == vel.kind && live.key == vel.key =>
+            {
+                live.patch(vel);
+                Ok(())
+            }
+            (_, node) => Err(node),
+        }
+    }
+}
+
+impl LiveElement {
+    fn patch(&mut self, vel: VElement) {
+        for (name, _) in &self.attrs {
+            if !vel.attrs.iter().any(|(new, _)| new == name) {
+                self.el.remove_attr(name);
+            }
+        }
+        for (name, value) in &vel.attrs {
+            let old = self.attrs.iter().find(|(old, _)| old == name);
+            if old.is_none_or(|(_, old)| old != value) {
+                self.el.set_attr(name, value);
+            }
+        }
+        self.attrs = vel.attrs;
+
+        let mut listeners = mem::take(&mut self.listeners);
+        listeners.retain(|old| vel.listeners.iter().any(|(new, _)| *new == old.event_type));
+        for (event_type, f) in vel.listeners {
+            match listeners.iter().find(|old| old.event_type == event_type) {
+                Some(old) => *old.handler.borrow_mut() = Some(f),
+                None => listeners.push(LiveListener::new(&self.el, event_type, f)),
+            }
+        }
+        self.listeners = listeners;
+
+        patch_children(&self.el, &mut self.children, vel.children);
+    }
+}
+
+impl LiveListener {
+    fn new(el: &Element, event_type: String, f: Box<dyn FnMut(Event)>) -> Self {
+        let handler: Handler = Rc::new(RefCell::new(Some(f)));
+        let handler2 = handler.clone();
+        let _listener = EventListener::listen(el, &event_type, move |ev| {
+            // Take the handler out while it runs, so that it may re-render and
+            // replace itself.
+            let f = handler2.borrow_mut().take();
+            if let Some(mut f) = f {
+                f(ev);
+                let mut slot = handler2.borrow_mut();
+                if slot.is_none() {
+                    *slot = Some(f);
+                }
+            }
+        });
+        Self {
+            event_type,
+            handler,
+            _listener,
+        }
+    }
+}
+
+/// Update the children of `parent` to match `new`.
+///
+/// Keyed children are matched by key, and the remaining children are matched
+/// in order. Matched nodes are patched and moved into place, and unmatched
+/// nodes are created or removed.
+fn patch_children(parent: &Element, children: &mut Vec<Live>, new: Vec<VNode>) {
+    let mut old: Vec<Option<Live>> = mem::take(children).into_iter().map(Some).collect();
+    let keyed: HashMap<String, usize> = old
+        .iter()
+        .enumerate()
+        .filter_map(|(i, live)| Some((live.as_ref()?.key()?.to_owned(), i)))
+        .collect();
+    let mut unkeyed = old
+        .iter()
+        .enumerate()
+        .filter(|(_, live)| live.as_ref().is_some_and(|live| live.key().is_none()))
+        .map(|(i, _)| i)
+        .collect::<Vec<_>>()
+        .into_iter();
+
+    for node in new {
+        let index = match &node {
+            VNode::Element(VElement { key: Some(key), .. }) => keyed.get(key).copied(),
+            _ => unkeyed.next(),
+        };
+        let live = match index.and_then(|i| old[i].take()) {
+            Some(mut live) => match live.patch(node) {
+                Ok(()) => live,
+                Err(node) => {
+                    live.remove();
+                    Live::create(node)
+                }
+            },
+            None => Live::create(node),
+        };
+        children.push(live);
+    }
+
+    for live in old.into_iter().flatten() {
+        live.remove();
+    }
+
+    // Move every node into place, skipping those which already are.
+    let parent: &web_sys::Node = parent.as_ref();
+    let nodes = parent.child_nodes();
+    for (i, live) in children.iter().enumerate() {
+        let current = nodes.item(i as u32);
+        let in_place = current
+            .as_ref()
+            .is_some_and(|node| node.is_same_node(Some(live.node())));
+        if !in_place {
+            parent
+                .insert_before(live.node(), current.as_ref())
+                .unwrap_throw();
+        }
+    }
+}
@@ -0,0 +1,149 @@
+use crate::events::Event;
+
+use std::borrow::Cow;
+use std::fmt::{self, Debug};
+
+/// A virtual node, describing what part of the DOM should look like.
+///
+/// Virtual nodes are cheap to create, and are turned into real nodes by
+/// rendering them into a [`VDom`].
+///
+/// [`VDom`]: crate::dom::VDom
+#[derive(Debug)]
+pub enum VNode {
+    /// An element.
+    Element(VElement),
+    /// A text node.
+    Text(String),
+}
+
+impl VNode {
+    /// Create a new text node.
+    pub fn text(text: &str) -> Self {
+        VNode::Text(text.to_owned())
+    }
+}
+
+impl From<VElement> for VNode {
+    fn from(el: VElement) -> Self {
+        VNode::Element(el)
+    }
+}
+
+impl From<&str> for VNode {
+    fn from(text: &str) -> Self {
+        VNode::text(text)
+    }
+}
+
+impl From<String> for VNode {
+    fn from(text: String) -> Self {
+        VNode::Text(text)
+    }
+}
+
+/// An event type, and the function to call when it's dispatched.
+pub(crate) type Listener = (String, Box<dyn FnMut(Event)>);
+
+/// A virtual element.
+///
+/// # Examples
+///
+/// ```no_run
+/// use localghost::dom::VElement;
+///
+/// let todos = ["milk", "eggs"];
+/// let list = VElement::new("ul")
+///     .attr("class", "todos")
+///     .children(todos.iter().map(|todo| VElement::new("li").key(todo).text(todo)));
+/// ```
+pub struct VElement {
+    pub(crate) kind: Cow<'static, str>,
+    pub(crate) key: Option<String>,
+    pub(crate) attrs: Vec<(String, String)>,
+    pub(crate) listeners: Vec<Listener>,
+    pub(crate) children: Vec<VNode>,
+}
+
+impl VElement {
+    /// Create a new instance.
+    pub fn new<S>(kind: S) -> Self
+    where
+        S: Into<Cow<'static, str>>,
+    {
+        Self {
+            kind: kind.into(),
+            key: None,
+            attrs: vec![],
+            listeners: vec![],
+            children: vec![],
+        }
+    }
+
+    /// Set the key which identifies the element among its siblings.
+    ///
+    /// Keyed elements keep their DOM node when their siblings are added,
+    /// removed or reordered.
+    pub fn key<K>(mut self, key: K) -> Self
+    where
+        K: ToString,
+    {
+        self.key = Some(key.to_string());
+        self
+    }
+
+    /// Set an attribute.
+    pub fn attr(mut self, name: &str, value: &str) -> Self {
+        self.attrs.retain(|(key, _)| key != name);
+        self.attrs.push((name.to_owned(), value.to_owned()));
+        self
+    }
+
+    /// Register an event listener. Registering a second listener for the same
+    /// event replaces the first.
+    pub fn on<F>(mut self, event_type: &str, f: F) -> Self
+    where
+        F: FnMut(Event) + 'static,
+    {
+        self.listeners.retain(|(key, _)| key != event_type);
+        self.listeners.push((event_type.to_owned(), Box::new(f)));
+        self
+    }
+
+    /// Append a child node.
+    pub fn child<N>(mut self, child: N) -> Self
+    where
+        N: Into<VNode>,
+    {
+        self.children.push(child.into());
+        self
+    }
+
+    /// Append several child nodes.
+    pub fn children<I>(mut self, children: I) -> Self
+    where
+        I: IntoIterator,
+        I::Item: Into<VNode>,
+    {
+        self.children.extend(children.into_iter().map(Into::into));
+        self
+    }
+
+    /// Append a text node.
+    pub fn text(self, text: &str) -> Self {
+        self.child(VNode::text(text))
+    }
+}
+
+impl Debug for VElement {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let listeners: Vec<_> = self.listeners.iter().map(|(key, _)| key).collect();
+        f.debug_struct("VElement")
+            .field("kind", &self.kind)
+            .field("key", &self.key)
+            .field("attrs", &self.attrs)
+            .field("listeners", &listeners)
+            .field("children", &self.children)
+            .finish()
+    }
+}
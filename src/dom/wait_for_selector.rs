@@ -0,0 +1,107 @@
+use crate::dom::{Element, Mutations};
+use crate::utils::{document, ResultExt};
+
+use futures_core::Stream;
+use futures_timer::Delay;
+
+use std::future::{poll_fn, Future};
+use std::io;
+use std::pin::Pin;
+use std::task::Poll;
+use std::time::Duration;
+
+/// Wait until an element matching `selectors` exists in the document, and
+/// return the first one.
+///
+/// Resolves right away if a matching element already exists. Otherwise the
+/// document is observed until an element is added, or an attribute changes,
+/// such that it matches.
+///
+/// # Errors
+///
+/// An error of kind `io::ErrorKind::TimedOut` is returned if no element
+/// matched within `timeout`, and `io::ErrorKind::InvalidInput` if the
+/// selectors are invalid.
+///
+/// # Examples
+///
+/// ```no_run
+/// use localghost::prelude::*;
+/// use localghost::{dom, log};
+/// use std::io;
+/// use std::time::Duration;
+///
+/// #[localghost::main]
+/// async fn main() -> io::Result<()> {
+///     let el = dom::wait_for_selector("#app .ready", Duration::from_secs(5)).await?;
+///     log::info!("rendered: {:?}", el.text());
+///     Ok(())
+/// }
+/// ```
+pub async fn wait_for_selector(selectors: &str, timeout: Duration) -> io::Result<Element> {
+    wait_until(selectors, timeout, || query(selectors)).await
+}
+
+/// Wait until no element matching `selectors` exists in the document.
+///
+/// Resolves right away if no element matches. Otherwise the document is
+/// observed until every matching element is removed, or changed such that it
+/// no longer matches.
+///
+/// # Errors
+///
+/// An error of kind `io::ErrorKind::TimedOut` is returned if an element still
+/// matched after `timeout`, and `io::ErrorKind::InvalidInput` if the selectors
+/// are invalid.
+pub async fn wait_for_selector_removed(selectors: &str, timeout: Duration) -> io::Result<()> {
+    wait_until(selectors, timeout, || match query(selectors)? {
+        Some(_) => Ok(None),
+        None => Ok(Some(())),
+    })
+    .await
+}
+
+/// Find the first element matching `selectors`.
+fn query(selectors: &str) -> io::Result<Option<Element>> {
+    let el = document()
+        .query_selector(selectors)
+        .err_kind(io::ErrorKind::InvalidInput)?;
+    Ok(el.map(Element::from_element))
+}
+
+/// Run `check` whenever the document changes, until it returns a value.
+async fn wait_until<T, F>(selectors: &str, timeout: Duration, mut check: F) -> io::Result<T>
+where
+    F: FnMut() -> io::Result<Option<T>>,
+{
+    if let Some(value) = check()? {
+        return Ok(value);
+    }
+
+    let mut mutations = Mutations::builder()
+        .child_list(true)
+        .attributes(true)
+        .subtree(true)
+        .observe(document())?;
+    let mut delay = Delay::new(timeout);
+    poll_fn(|cx| {
+        // Only query the document once per batch of mutations.
+        let mut changed = false;
+        while let Poll::Ready(Some(_)) = Pin::new(&mut mutations).poll_next(cx) {
+            changed = true;
+        }
+        if changed {
+            if let Some(value) = check()? {
+                return Poll::Ready(Ok(value));
+            }
+        }
+        match Pin::new(&mut delay).poll(cx) {
+            Poll::Ready(()) => Poll::Ready(Err(io::Error::new(
+                io::ErrorKind::TimedOut,
+                format!("Timed out waiting for `{}`", selectors),
+            ))),
+            Poll::Pending => Poll::Pending,
+        }
+    })
+    .await
+}
@@ -20,6 +20,13 @@ impl Event {
         self.inner.target().and_then(|t| t.dyn_into::<T>().ok())
     }
 
+    /// Get the element the event was dispatched to, if it was dispatched to
+    /// an element.
+    pub fn target_element(&self) -> Option<crate::dom::Element> {
+        self.target::<web_sys::Element>()
+            .map(crate::dom::Element::from_element)
+    }
+
     /// Convert this type into its raw counterpart.
     pub fn into_raw(self) -> web_sys::Event {
         self.inner
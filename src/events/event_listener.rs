@@ -173,6 +173,22 @@ impl EventListener {
         self.f.take().unwrap_throw().forget()
     }
 
+    /// Keeps the `EventListener` registered for as long as its target
+    /// exists.
+    ///
+    /// Ownership of the listener is handed to the target. Its memory is only
+    /// reclaimed once the target is garbage collected if weak references are
+    /// enabled, by building with `wasm-bindgen --weak-refs`. Otherwise it
+    /// leaks just like [`forget`], so avoid calling this for targets which
+    /// are created over and over.
+    ///
+    /// [`forget`]: EventListener::forget
+    #[inline]
+    pub fn attach(mut self) {
+        // This will never panic, because `f` is always `Some`
+        let _ = self.f.take().unwrap_throw().into_js_value();
+    }
+
     /// Returns the `EventTarget`.
     #[inline]
     pub fn target(&self) -> &web_sys::EventTarget {
@@ -6,7 +6,7 @@ use std::task::{Context, Poll};
 use crate::events::{Event, EventListener};
 use crate::prelude::*;
 
-use async_channel::{bounded, Receiver};
+use async_channel::{bounded, unbounded, Receiver};
 use futures_core::{ready, Stream};
 use pin_project::pin_project;
 
@@ -25,8 +25,11 @@ pub trait EventTarget: AsRef<web_sys::EventTarget> {
         F: FnOnce(Event) + 'static;
 
     /// Wait for a stream of events.
+    ///
+    /// Events are buffered until the stream is polled, so a stream which is
+    /// never polled keeps every event it receives.
     fn on(&self, event_type: &str) -> EventStream {
-        let (sender, receiver) = bounded(1);
+        let (sender, receiver) = unbounded();
         let listener = EventListener::listen(self, event_type, move |ev| {
             sender.try_send(ev).unwrap_throw()
         });
@@ -0,0 +1,85 @@
+use crate::events::EventListener;
+use crate::fs::indexed_db::{dom_error, from_js};
+use crate::prelude::*;
+
+use async_channel::{self as channel, Receiver};
+use futures_core::{ready, Stream};
+use serde::de::DeserializeOwned;
+use wasm_bindgen::JsCast;
+
+use std::io;
+use std::marker::PhantomData;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+
+/// A stream over the keys and values of an [`ObjectStore`] or [`Index`].
+///
+/// Entries are read as fast as the database can provide them, so the
+/// transaction stays alive even if the stream is polled slowly.
+///
+/// This `struct` is created by the [`cursor`] method on [`ObjectStore`] and
+/// [`Index`]. See their documentation for more.
+///
+/// [`ObjectStore`]: crate::fs::ObjectStore
+/// [`Index`]: crate::fs::Index
+/// [`cursor`]: crate::fs::ObjectStore::cursor
+#[pin_project::pin_project]
+#[derive(Debug)]
+#[must_use = "Streams do nothing unless polled"]
+pub struct Cursor<K, V> {
+    _listeners: Vec<EventListener>,
+    #[pin]
+    receiver: Receiver<io::Result<(JsValue, JsValue)>>,
+    _types: PhantomData<fn() -> (K, V)>,
+}
+
+impl<K, V> Cursor<K, V> {
+    /// Create a new instance from a request which opens a cursor.
+    pub(crate) fn new(req: web_sys::IdbRequest) -> Self {
+        let (sender, receiver) = channel::unbounded();
+        let sender2 = sender.clone();
+        let req2 = req.clone();
+        let success = EventListener::listen(&req, "success", move |_| {
+            let cursor = req2.result().unwrap_throw();
+            if cursor.is_null() {
+                sender.close();
+                return;
+            }
+            let cursor: web_sys::IdbCursorWithValue = cursor.unchecked_into();
+            let entry = (cursor.key().unwrap_throw(), cursor.value().unwrap_throw());
+            let _ = sender.try_send(Ok(entry));
+            cursor.continue_().unwrap_throw();
+        });
+
+        let req2 = req.clone();
+        let error = EventListener::listen(&req, "error", move |_| {
+            let _ = sender2.try_send(Err(dom_error(req2.error().ok().flatten())));
+            sender2.close();
+        });
+
+        Self {
+            _listeners: vec![success, error],
+            receiver,
+            _types: PhantomData,
+        }
+    }
+}
+
+impl<K, V> Stream for Cursor<K, V>
+where
+    K: DeserializeOwned,
+    V: DeserializeOwned,
+{
+    type Item = io::Result<(K, V)>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = self.project();
+        let entry = match ready!(this.receiver.poll_next(cx)) {
+            Some(entry) => entry,
+            None => return Poll::Ready(None),
+        };
+        Poll::Ready(Some(
+            entry.and_then(|(key, val)| Ok((from_js(&key)?, from_js(&val)?))),
+        ))
+    }
+}
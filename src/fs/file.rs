@@ -0,0 +1,225 @@
+use crate::dom::Element;
+use crate::events::Event;
+use crate::prelude::*;
+use crate::utils::ResultExt;
+
+use futures_core::{ready, Stream};
+use js_sys::{ArrayBuffer, Uint8Array};
+use wasm_bindgen::JsCast;
+use wasm_bindgen_futures::JsFuture;
+
+use std::fmt::{self, Debug};
+use std::future::Future;
+use std::io;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+use std::time::Duration;
+
+/// A file selected by the user, such as through an `<input type="file">`
+/// element or by dropping it on the page.
+///
+/// [Read more](https://developer.mozilla.org/en-US/docs/Web/API/File)
+///
+/// # Examples
+///
+/// ```no_run
+/// use localghost::prelude::*;
+/// use localghost::dom::{body, Element};
+/// use localghost::{fs, log};
+/// use async_std::prelude::*;
+///
+/// #[localghost::main]
+/// async fn main() {
+///     let input = Element::new("input");
+///     input.set_attr("type", "file");
+///     body().append(&input);
+///
+///     let mut changes = input.on("change");
+///     while changes.next().await.is_some() {
+///         for file in fs::File::from_input(&input) {
+///             log::info!("{}: {} bytes of {}", file.name(), file.size(), file.mime_type());
+///         }
+///     }
+/// }
+/// ```
+#[derive(Debug, Clone)]
+pub struct File {
+    inner: web_sys::File,
+}
+
+impl File {
+    /// Create a new instance from a raw `web_sys::File`.
+    pub fn from_raw(inner: web_sys::File) -> Self {
+        Self { inner }
+    }
+
+    /// Get the files selected in an `<input type="file">` element.
+    ///
+    /// Returns an empty `Vec` if the element is not an input element.
+    pub fn from_input(input: &Element) -> Vec<Self> {
+        match input.as_raw().dyn_ref::<web_sys::HtmlInputElement>() {
+            Some(input) => from_list(input.files()),
+            None => vec![],
+        }
+    }
+
+    /// Get the files selected in the `<input type="file">` element which
+    /// dispatched a `change` or `input` event.
+    ///
+    /// Returns an empty `Vec` if the event target is not an input element.
+    pub fn from_input_event(ev: &Event) -> Vec<Self> {
+        match ev.target::<web_sys::HtmlInputElement>() {
+            Some(input) => from_list(input.files()),
+            None => vec![],
+        }
+    }
+
+    /// Get the files dropped on the page in a `drop` event.
+    ///
+    /// Returns an empty `Vec` if the event is not a drag event, or didn't
+    /// carry any files. Remember to prevent the default action of `dragover`
+    /// events, or the browser will open the files instead.
+    pub fn from_drop_event(ev: &Event) -> Vec<Self> {
+        let ev: &web_sys::Event = ev.as_ref();
+        match ev.dyn_ref::<web_sys::DragEvent>() {
+            Some(ev) => from_list(ev.data_transfer().and_then(|data| data.files())),
+            None => vec![],
+        }
+    }
+
+    /// Get the name of the file, without its path.
+    pub fn name(&self) -> String {
+        self.inner.name()
+    }
+
+    /// Get the size of the file in bytes.
+    pub fn size(&self) -> u64 {
+        self.inner.size() as u64
+    }
+
+    /// Get the MIME type of the file, or an empty string if it's unknown.
+    pub fn mime_type(&self) -> String {
+        self.inner.type_()
+    }
+
+    /// Get when the file was last modified, as the time since the Unix epoch.
+    pub fn last_modified(&self) -> Duration {
+        Duration::from_millis(self.inner.last_modified() as u64)
+    }
+
+    /// Read the entire contents of the file into a bytes vector.
+    ///
+    /// # Errors
+    ///
+    /// An error is returned if the file could not be read, such as when it
+    /// was changed on disk after it was selected.
+    pub async fn read_to_vec(&self) -> io::Result<Vec<u8>> {
+        read_blob(&self.inner).await
+    }
+
+    /// Read the entire contents of the file into a string.
+    ///
+    /// # Errors
+    ///
+    /// An error of kind `io::ErrorKind::InvalidData` is returned if the file is
+    /// not valid UTF-8.
+    pub async fn read_to_string(&self) -> io::Result<String> {
+        let bytes = self.read_to_vec().await?;
+        String::from_utf8(bytes).map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err))
+    }
+
+    /// Read the file in chunks of up to `chunk_size` bytes.
+    ///
+    /// Only one chunk is held in memory at a time, which makes this suitable
+    /// for large files.
+    ///
+    /// # Panics
+    ///
+    /// This function will panic if `chunk_size` is `0`.
+    pub fn chunks(&self, chunk_size: usize) -> Chunks {
+        assert!(chunk_size > 0, "chunk size must be non-zero");
+        Chunks {
+            file: self.inner.clone(),
+            offset: 0,
+            chunk_size: chunk_size as u64,
+            next: None,
+        }
+    }
+
+    /// Access the raw `web_sys::File`.
+    pub fn as_raw(&self) -> &web_sys::File {
+        &self.inner
+    }
+}
+
+/// A stream over the contents of a [`File`].
+///
+/// This `struct` is created by the [`chunks`] method on [`File`]. See its
+/// documentation for more.
+///
+/// [`chunks`]: File::chunks
+#[must_use = "Streams do nothing unless polled"]
+pub struct Chunks {
+    file: web_sys::File,
+    offset: u64,
+    chunk_size: u64,
+    next: Option<JsFuture>,
+}
+
+impl Debug for Chunks {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Chunks")
+            .field("file", &self.file.name())
+            .field("offset", &self.offset)
+            .field("chunk_size", &self.chunk_size)
+            .finish()
+    }
+}
+
+impl Stream for Chunks {
+    type Item = io::Result<Vec<u8>>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let size = self.file.size() as u64;
+        if self.next.is_none() {
+            if self.offset >= size {
+                return Poll::Ready(None);
+            }
+            let end = (self.offset + self.chunk_size).min(size);
+            let chunk = self
+                .file
+                .slice_with_f64_and_f64(self.offset as f64, end as f64)
+                .unwrap_throw();
+            self.offset = end;
+            self.next = Some(JsFuture::from(chunk.array_buffer()));
+        }
+
+        let res = ready!(Pin::new(self.next.as_mut().unwrap_throw()).poll(cx));
+        self.next = None;
+        Poll::Ready(Some(res.err_kind(io::ErrorKind::Other).map(to_vec)))
+    }
+}
+
+/// Read the entire contents of a blob.
+async fn read_blob(blob: &web_sys::Blob) -> io::Result<Vec<u8>> {
+    let buf = JsFuture::from(blob.array_buffer())
+        .await
+        .err_kind(io::ErrorKind::Other)?;
+    Ok(to_vec(buf))
+}
+
+fn to_vec(buf: JsValue) -> Vec<u8> {
+    let buf: ArrayBuffer = buf.unchecked_into();
+    Uint8Array::new(&buf).to_vec()
+}
+
+fn from_list(list: Option<web_sys::FileList>) -> Vec<File> {
+    let list = match list {
+        Some(list) => list,
+        None => return vec![],
+    };
+    (0..list.length())
+        .filter_map(|i| list.item(i))
+        .map(File::from_raw)
+        .collect()
+}
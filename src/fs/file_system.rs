@@ -0,0 +1,346 @@
+use crate::fs::{storage_manager::manager, Metadata, ReadDir};
+use crate::prelude::*;
+use crate::utils::ResultExt;
+
+use js_sys::{ArrayBuffer, Uint8Array};
+use wasm_bindgen::JsCast;
+use wasm_bindgen_futures::JsFuture;
+use web_sys::{FileSystemDirectoryHandle, FileSystemFileHandle};
+
+use std::io;
+
+/// Read the entire contents of a file into a bytes vector.
+///
+/// Paths are relative to the root of the origin's private file system, and
+/// use `/` as a separator.
+///
+/// [Read more](https://developer.mozilla.org/en-US/docs/Web/API/File_System_API/Origin_private_file_system)
+///
+/// # Errors
+///
+/// An error of kind `io::ErrorKind::NotFound` is returned if the file
+/// doesn't exist, and `io::ErrorKind::IsADirectory` if the path points to a
+/// directory.
+///
+/// # Examples
+///
+/// ```no_run
+/// use localghost::prelude::*;
+/// use localghost::{fs, log};
+/// use std::io;
+///
+/// #[localghost::main]
+/// async fn main() -> io::Result<()> {
+///     fs::create_dir_all("notes/2021").await?;
+///     fs::write("notes/2021/todo.txt", "buy milk").await?;
+///     let todo = fs::read_to_string("notes/2021/todo.txt").await?;
+///     log::info!("todo: {}", todo);
+///     Ok(())
+/// }
+/// ```
+pub async fn read(path: &str) -> io::Result<Vec<u8>> {
+    let file = file(path, false).await?;
+    let buf = call(file.array_buffer(), io::ErrorKind::Other).await?;
+    let buf: ArrayBuffer = buf.unchecked_into();
+    Ok(Uint8Array::new(&buf).to_vec())
+}
+
+/// Read the entire contents of a file into a string.
+///
+/// # Errors
+///
+/// An error of kind `io::ErrorKind::InvalidData` is returned if the file is
+/// not valid UTF-8. See [`read`] for other errors.
+pub async fn read_to_string(path: &str) -> io::Result<String> {
+    let bytes = read(path).await?;
+    String::from_utf8(bytes).map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err))
+}
+
+/// Write a slice as the entire contents of a file, creating it if it doesn't
+/// exist.
+///
+/// # Errors
+///
+/// An error of kind `io::ErrorKind::NotFound` is returned if the parent
+/// directory doesn't exist, `io::ErrorKind::IsADirectory` if the path points
+/// to a directory, and `io::ErrorKind::InvalidInput` if the file name isn't
+/// allowed.
+pub async fn write<C>(path: &str, contents: C) -> io::Result<()>
+where
+    C: AsRef<[u8]>,
+{
+    let (parent, name) = split(path)?;
+    let handle = file_handle(&dir(&parent, false).await?, name, true).await?;
+    let stream = call(handle.create_writable(), io::ErrorKind::PermissionDenied).await?;
+    let stream: web_sys::FileSystemWritableFileStream = stream.unchecked_into();
+    let written = stream
+        .write_with_u8_array(contents.as_ref())
+        .dom_err_kind(io::ErrorKind::Other)?;
+    if let Err(err) = call(written, io::ErrorKind::Other).await {
+        let _ = stream.abort();
+        return Err(err);
+    }
+    call(stream.close(), io::ErrorKind::Other).await?;
+    Ok(())
+}
+
+/// Create a new, empty directory.
+///
+/// # Errors
+///
+/// An error of kind `io::ErrorKind::AlreadyExists` is returned if the path
+/// already exists, and `io::ErrorKind::NotFound` if the parent directory
+/// doesn't exist.
+pub async fn create_dir(path: &str) -> io::Result<()> {
+    let (parent, name) = split(path)?;
+    let parent = dir(&parent, false).await?;
+    match entry(&parent, name).await {
+        Ok(_) => Err(io::Error::new(
+            io::ErrorKind::AlreadyExists,
+            format!("fs: {} already exists", path),
+        )),
+        Err(err) if err.kind() == io::ErrorKind::NotFound => {
+            dir_handle(&parent, name, true).await?;
+            Ok(())
+        }
+        Err(err) => Err(err),
+    }
+}
+
+/// Create a directory and all of its missing parents.
+///
+/// # Errors
+///
+/// An error of kind `io::ErrorKind::NotADirectory` is returned if a file
+/// exists at any point of the path.
+pub async fn create_dir_all(path: &str) -> io::Result<()> {
+    dir(&components(path)?, true).await?;
+    Ok(())
+}
+
+/// Remove a file.
+///
+/// # Errors
+///
+/// An error of kind `io::ErrorKind::NotFound` is returned if the file
+/// doesn't exist, and `io::ErrorKind::IsADirectory` if the path points to a
+/// directory.
+pub async fn remove_file(path: &str) -> io::Result<()> {
+    let (parent, name) = split(path)?;
+    let parent = dir(&parent, false).await?;
+    file_handle(&parent, name, false).await?;
+    remove_entry(&parent, name, false).await
+}
+
+/// Remove an empty directory.
+///
+/// # Errors
+///
+/// An error of kind `io::ErrorKind::DirectoryNotEmpty` is returned if the
+/// directory isn't empty, and `io::ErrorKind::NotADirectory` if the path
+/// points to a file.
+pub async fn remove_dir(path: &str) -> io::Result<()> {
+    let (parent, name) = split(path)?;
+    let parent = dir(&parent, false).await?;
+    dir_handle(&parent, name, false).await?;
+    remove_entry(&parent, name, false).await
+}
+
+/// Remove a directory, after removing all of its contents.
+///
+/// # Errors
+///
+/// An error of kind `io::ErrorKind::NotFound` is returned if the directory
+/// doesn't exist, and `io::ErrorKind::NotADirectory` if the path points to a
+/// file.
+pub async fn remove_dir_all(path: &str) -> io::Result<()> {
+    let (parent, name) = split(path)?;
+    let parent = dir(&parent, false).await?;
+    dir_handle(&parent, name, false).await?;
+    remove_entry(&parent, name, true).await
+}
+
+/// Rename a file, replacing the destination if it already exists.
+///
+/// The file is copied to its new location, and then removed, so renaming
+/// large files is slow.
+///
+/// # Errors
+///
+/// An error of kind `io::ErrorKind::Unsupported` is returned if the path
+/// points to a directory. See [`read`] and [`write`] for other errors.
+pub async fn rename(from: &str, to: &str) -> io::Result<()> {
+    if metadata(from).await?.is_dir() {
+        return Err(io::Error::new(
+            io::ErrorKind::Unsupported,
+            format!("fs: unable to rename directory {}", from),
+        ));
+    }
+    if components(from)? == components(to)? {
+        return Ok(());
+    }
+    write(to, read(from).await?).await?;
+    remove_file(from).await
+}
+
+/// Query the metadata of a file or directory.
+///
+/// # Errors
+///
+/// An error of kind `io::ErrorKind::NotFound` is returned if the path
+/// doesn't exist.
+pub async fn metadata(path: &str) -> io::Result<Metadata> {
+    let components = components(path)?;
+    let (parent, name) = match components.split_last() {
+        Some((name, parent)) => (parent, *name),
+        None => return Ok(Metadata::dir()),
+    };
+    let parent = dir(parent, false).await?;
+    match entry(&parent, name).await? {
+        Entry::File(handle) => {
+            let file = call(handle.get_file(), io::ErrorKind::Other).await?;
+            Ok(Metadata::file(&file.unchecked_into()))
+        }
+        Entry::Dir => Ok(Metadata::dir()),
+    }
+}
+
+/// Stream the entries of a directory.
+///
+/// # Errors
+///
+/// An error of kind `io::ErrorKind::NotFound` is returned if the directory
+/// doesn't exist, and `io::ErrorKind::NotADirectory` if the path points to a
+/// file.
+///
+/// # Examples
+///
+/// ```no_run
+/// use localghost::prelude::*;
+/// use localghost::{fs, log};
+/// use async_std::prelude::*;
+/// use std::io;
+///
+/// #[localghost::main]
+/// async fn main() -> io::Result<()> {
+///     let mut entries = fs::read_dir("/").await?;
+///     while let Some(entry) = entries.next().await.transpose()? {
+///         log::info!("{} (directory: {})", entry.path(), entry.is_dir());
+///     }
+///     Ok(())
+/// }
+/// ```
+pub async fn read_dir(path: &str) -> io::Result<ReadDir> {
+    let components = components(path)?;
+    let handle = dir(&components, false).await?;
+    Ok(ReadDir::new(components.join("/"), handle))
+}
+
+/// An existing file or directory.
+enum Entry {
+    File(FileSystemFileHandle),
+    Dir,
+}
+
+/// Wait for a promise, and convert its rejection to an `io::Error`.
+pub(crate) async fn call(promise: js_sys::Promise, fallback: io::ErrorKind) -> io::Result<JsValue> {
+    JsFuture::from(promise).await.dom_err_kind(fallback)
+}
+
+/// Split a path into its components.
+fn components(path: &str) -> io::Result<Vec<&str>> {
+    let components: Vec<_> = path
+        .split('/')
+        .filter(|c| !c.is_empty() && *c != ".")
+        .collect();
+    if components.contains(&"..") {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            format!("fs: {} may not contain `..`", path),
+        ));
+    }
+    Ok(components)
+}
+
+/// Split a path into the components of its parent, and its file name.
+fn split(path: &str) -> io::Result<(Vec<&str>, &str)> {
+    let mut components = components(path)?;
+    match components.pop() {
+        Some(name) => Ok((components, name)),
+        None => Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            format!("fs: {} has no file name", path),
+        )),
+    }
+}
+
+/// Get the root directory of the origin's private file system.
+async fn root() -> io::Result<FileSystemDirectoryHandle> {
+    let root = call(manager()?.get_directory(), io::ErrorKind::PermissionDenied).await?;
+    Ok(root.unchecked_into())
+}
+
+/// Get the directory at a path.
+async fn dir(components: &[&str], create: bool) -> io::Result<FileSystemDirectoryHandle> {
+    let mut dir = root().await?;
+    for name in components {
+        dir = dir_handle(&dir, name, create).await?;
+    }
+    Ok(dir)
+}
+
+/// Get the file at a path.
+async fn file(path: &str, create: bool) -> io::Result<web_sys::File> {
+    let (parent, name) = split(path)?;
+    let handle = file_handle(&dir(&parent, false).await?, name, create).await?;
+    let file = call(handle.get_file(), io::ErrorKind::Other).await?;
+    Ok(file.unchecked_into())
+}
+
+async fn dir_handle(
+    parent: &FileSystemDirectoryHandle,
+    name: &str,
+    create: bool,
+) -> io::Result<FileSystemDirectoryHandle> {
+    let options = web_sys::FileSystemGetDirectoryOptions::new();
+    options.set_create(create);
+    let promise = parent.get_directory_handle_with_options(name, &options);
+    let handle = call(promise, io::ErrorKind::NotADirectory).await?;
+    Ok(handle.unchecked_into())
+}
+
+async fn file_handle(
+    parent: &FileSystemDirectoryHandle,
+    name: &str,
+    create: bool,
+) -> io::Result<FileSystemFileHandle> {
+    let options = web_sys::FileSystemGetFileOptions::new();
+    options.set_create(create);
+    let promise = parent.get_file_handle_with_options(name, &options);
+    let handle = call(promise, io::ErrorKind::IsADirectory).await?;
+    Ok(handle.unchecked_into())
+}
+
+/// Get the handle of an existing file or directory.
+async fn entry(parent: &FileSystemDirectoryHandle, name: &str) -> io::Result<Entry> {
+    match file_handle(parent, name, false).await {
+        Ok(handle) => Ok(Entry::File(handle)),
+        Err(err) if err.kind() == io::ErrorKind::IsADirectory => {
+            dir_handle(parent, name, false).await?;
+            Ok(Entry::Dir)
+        }
+        Err(err) => Err(err),
+    }
+}
+
+async fn remove_entry(
+    parent: &FileSystemDirectoryHandle,
+    name: &str,
+    recursive: bool,
+) -> io::Result<()> {
+    let options = web_sys::FileSystemRemoveOptions::new();
+    options.set_recursive(recursive);
+    let promise = parent.remove_entry_with_options(name, &options);
+    call(promise, io::ErrorKind::DirectoryNotEmpty).await?;
+    Ok(())
+}
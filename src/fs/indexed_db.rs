@@ -0,0 +1,365 @@
+use crate::events::EventListener;
+use crate::fs::{ObjectStore, Transaction, TransactionMode};
+use crate::prelude::*;
+use crate::utils::{dom_error_kind, window, ResultExt};
+
+use async_channel::bounded;
+use serde::{de::DeserializeOwned, Serialize};
+use wasm_bindgen::JsCast;
+
+use std::cell::RefCell;
+use std::collections::BTreeMap;
+use std::fmt::{self, Debug};
+use std::io;
+use std::rc::Rc;
+
+type UpgradeFn = Box<dyn FnOnce(&Upgrade) -> io::Result<()>>;
+
+/// A connection to an IndexedDB database.
+///
+/// Values and keys are converted to and from JavaScript through `serde`. The
+/// connection is closed when it's dropped, or when another document needs to
+/// upgrade the database.
+///
+/// [Read more](https://developer.mozilla.org/en-US/docs/Web/API/IndexedDB_API)
+///
+/// # Examples
+///
+/// ```no_run
+/// use localghost::prelude::*;
+/// use localghost::fs::{IndexedDb, TransactionMode};
+/// use localghost::log;
+/// use serde::{Deserialize, Serialize};
+/// use std::io;
+///
+/// #[derive(Debug, Serialize, Deserialize)]
+/// struct Todo {
+///     id: u32,
+///     title: String,
+///     done: bool,
+/// }
+///
+/// #[localghost::main]
+/// async fn main() -> io::Result<()> {
+///     let db = IndexedDb::builder("app")
+///         .upgrade(1, |upgrade| {
+///             let todos = upgrade.create_object_store("todos", Some("id"), false)?;
+///             todos.create_index("done", "done", false)?;
+///             Ok(())
+///         })
+///         .open()
+///         .await?;
+///
+///     let tx = db.transaction(&["todos"], TransactionMode::ReadWrite)?;
+///     let todos = tx.object_store("todos")?;
+///     todos.put(&Todo { id: 1, title: "Write docs".into(), done: false }).await?;
+///     let todo: Option<Todo> = todos.get(&1).await?;
+///     log::info!("todo: {:?}", todo);
+///     tx.commit().await
+/// }
+/// ```
+#[derive(Debug)]
+pub struct IndexedDb {
+    db: web_sys::IdbDatabase,
+    _listener: EventListener,
+}
+
+impl IndexedDb {
+    /// Open a database at its current version, creating it if it doesn't
+    /// exist.
+    ///
+    /// # Errors
+    ///
+    /// An error of kind `io::ErrorKind::PermissionDenied` is returned if
+    /// IndexedDB is unavailable.
+    pub async fn open(name: &str) -> io::Result<Self> {
+        Self::builder(name).open().await
+    }
+
+    /// Create a builder to open a database with upgrades.
+    pub fn builder(name: &str) -> IndexedDbBuilder {
+        IndexedDbBuilder {
+            name: name.to_owned(),
+            upgrades: BTreeMap::new(),
+        }
+    }
+
+    /// Delete a database.
+    ///
+    /// # Errors
+    ///
+    /// An error of kind `io::ErrorKind::PermissionDenied` is returned if
+    /// IndexedDB is unavailable.
+    pub async fn delete(name: &str) -> io::Result<()> {
+        let req = factory()?
+            .delete_database(name)
+            .err_kind(io::ErrorKind::Other)?;
+        request(&req).await?;
+        Ok(())
+    }
+
+    /// Get the name of the database.
+    pub fn name(&self) -> String {
+        self.db.name()
+    }
+
+    /// Get the version of the database.
+    pub fn version(&self) -> u32 {
+        self.db.version() as u32
+    }
+
+    /// Get the names of all object stores in the database.
+    pub fn object_store_names(&self) -> Vec<String> {
+        strings(&self.db.object_store_names())
+    }
+
+    /// Start a transaction on a set of object stores.
+    ///
+    /// # Errors
+    ///
+    /// An error of kind `io::ErrorKind::NotFound` is returned if any of the
+    /// object stores don't exist.
+    pub fn transaction(&self, stores: &[&str], mode: TransactionMode) -> io::Result<Transaction> {
+        let names = stores
+            .iter()
+            .map(|name| JsValue::from_str(name))
+            .collect::<js_sys::Array>();
+        let tx = self
+            .db
+            .transaction_with_str_sequence_and_mode(&names, mode.into_raw())
+            .err_kind(io::ErrorKind::NotFound)?;
+        Ok(Transaction::new(tx))
+    }
+}
+
+impl Drop for IndexedDb {
+    fn drop(&mut self) {
+        self.db.close();
+    }
+}
+
+/// A builder for opening an [`IndexedDb`] at a version.
+///
+/// This `struct` is created by the [`builder`] method on [`IndexedDb`]. See
+/// its documentation for more.
+///
+/// [`builder`]: IndexedDb::builder
+pub struct IndexedDbBuilder {
+    name: String,
+    upgrades: BTreeMap<u32, UpgradeFn>,
+}
+
+impl IndexedDbBuilder {
+    /// Register a closure which upgrades the database to `version`.
+    ///
+    /// When the database is opened, every closure for a version newer than
+    /// the stored one is run in order. The database is opened at the newest
+    /// registered version.
+    pub fn upgrade<F>(mut self, version: u32, f: F) -> Self
+    where
+        F: FnOnce(&Upgrade) -> io::Result<()> + 'static,
+    {
+        self.upgrades.insert(version, Box::new(f));
+        self
+    }
+
+    /// Open the database, running upgrades if needed.
+    ///
+    /// # Errors
+    ///
+    /// An error of kind `io::ErrorKind::PermissionDenied` is returned if
+    /// IndexedDB is unavailable. If an upgrade fails its error is returned,
+    /// and the database is left at its previous version.
+    pub async fn open(self) -> io::Result<IndexedDb> {
+        let factory = factory()?;
+        let req = match self.upgrades.keys().next_back() {
+            Some(version) => factory.open_with_u32(&self.name, *version),
+            None => factory.open(&self.name),
+        }
+        .err_kind(io::ErrorKind::InvalidInput)?;
+
+        let failure = Rc::new(RefCell::new(None));
+        let failure2 = failure.clone();
+        let req2 = req.clone();
+        let upgrades = self.upgrades;
+        let _upgrade = req.once_with("upgradeneeded", move |ev| {
+            let ev = ev
+                .into_raw()
+                .dyn_into::<web_sys::IdbVersionChangeEvent>()
+                .unwrap_throw();
+            let upgrade = Upgrade {
+                db: req2.result().unwrap_throw().unchecked_into(),
+                tx: req2.transaction().unwrap_throw(),
+                old_version: ev.old_version() as u32,
+            };
+            for (version, f) in upgrades {
+                if version <= upgrade.old_version {
+                    continue;
+                }
+                crate::log::debug!("IndexedDb({}): upgrading to {}", upgrade.db.name(), version);
+                if let Err(err) = f(&upgrade) {
+                    *failure2.borrow_mut() = Some(err);
+                    let _ = upgrade.tx.abort();
+                    break;
+                }
+            }
+        });
+
+        let name = self.name.clone();
+        let _blocked = req.once_with("blocked", move |_| {
+            crate::log::debug!("IndexedDb({}): blocked by another connection", name);
+        });
+
+        let db = match request(&req).await {
+            Ok(db) => db.unchecked_into::<web_sys::IdbDatabase>(),
+            Err(err) => return Err(failure.borrow_mut().take().unwrap_or(err)),
+        };
+
+        // Let other documents upgrade the database.
+        let db2 = db.clone();
+        let _listener = EventListener::listen(&db, "versionchange", move |_| db2.close());
+        Ok(IndexedDb { db, _listener })
+    }
+}
+
+impl Debug for IndexedDbBuilder {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("IndexedDbBuilder")
+            .field("name", &self.name)
+            .field("upgrades", &self.upgrades.keys().collect::<Vec<_>>())
+            .finish()
+    }
+}
+
+/// Access to a database's schema while it's being upgraded.
+///
+/// This `struct` is passed to the closures registered through the
+/// [`upgrade`] method on [`IndexedDbBuilder`].
+///
+/// [`upgrade`]: IndexedDbBuilder::upgrade
+#[derive(Debug)]
+pub struct Upgrade {
+    db: web_sys::IdbDatabase,
+    tx: web_sys::IdbTransaction,
+    old_version: u32,
+}
+
+impl Upgrade {
+    /// Get the version the database is being upgraded from.
+    ///
+    /// This is `0` if the database was just created.
+    pub fn old_version(&self) -> u32 {
+        self.old_version
+    }
+
+    /// Create a new object store.
+    ///
+    /// If `key_path` is set, keys are read from that field of each value.
+    /// Otherwise keys must be passed explicitly, unless `auto_increment` is
+    /// set.
+    ///
+    /// # Errors
+    ///
+    /// An error of kind `io::ErrorKind::AlreadyExists` is returned if an
+    /// object store with the same name already exists.
+    pub fn create_object_store(
+        &self,
+        name: &str,
+        key_path: Option<&str>,
+        auto_increment: bool,
+    ) -> io::Result<ObjectStore> {
+        let params = web_sys::IdbObjectStoreParameters::new();
+        params.set_key_path_opt_str(key_path);
+        params.set_auto_increment(auto_increment);
+        let store = self
+            .db
+            .create_object_store_with_optional_parameters(name, &params)
+            .err_kind(io::ErrorKind::AlreadyExists)?;
+        Ok(ObjectStore::new(store))
+    }
+
+    /// Delete an object store, and all of its values.
+    ///
+    /// # Errors
+    ///
+    /// An error of kind `io::ErrorKind::NotFound` is returned if the object
+    /// store doesn't exist.
+    pub fn delete_object_store(&self, name: &str) -> io::Result<()> {
+        self.db
+            .delete_object_store(name)
+            .err_kind(io::ErrorKind::NotFound)
+    }
+
+    /// Access an existing object store, such as to add indices to it.
+    ///
+    /// # Errors
+    ///
+    /// An error of kind `io::ErrorKind::NotFound` is returned if the object
+    /// store doesn't exist.
+    pub fn object_store(&self, name: &str) -> io::Result<ObjectStore> {
+        let store = self
+            .tx
+            .object_store(name)
+            .err_kind(io::ErrorKind::NotFound)?;
+        Ok(ObjectStore::new(store))
+    }
+}
+
+/// Get the `IDBFactory`.
+fn factory() -> io::Result<web_sys::IdbFactory> {
+    match window().indexed_db() {
+        Ok(Some(factory)) => Ok(factory),
+        _ => Err(io::Error::new(
+            io::ErrorKind::PermissionDenied,
+            "Unable to access IndexedDB.",
+        )),
+    }
+}
+
+/// Wait for a request to finish, and return its result.
+pub(crate) async fn request(req: &web_sys::IdbRequest) -> io::Result<JsValue> {
+    let (sender, receiver) = bounded(1);
+    let sender2 = sender.clone();
+    let _success = req.once_with("success", move |_| {
+        let _ = sender.try_send(true);
+    });
+    let _error = req.once_with("error", move |_| {
+        let _ = sender2.try_send(false);
+    });
+
+    if receiver.recv().await.unwrap_throw() {
+        req.result().err_kind(io::ErrorKind::Other)
+    } else {
+        Err(dom_error(req.error().ok().flatten()))
+    }
+}
+
+/// Convert an IndexedDB `DOMException` to an `io::Error`.
+pub(crate) fn dom_error(err: Option<web_sys::DomException>) -> io::Error {
+    let err = match err {
+        Some(err) => err,
+        None => return io::Error::new(io::ErrorKind::Interrupted, "IndexedDb: aborted"),
+    };
+    let kind = dom_error_kind(&err).unwrap_or(io::ErrorKind::Other);
+    io::Error::new(kind, format!("IndexedDb: {}", err.message()))
+}
+
+/// Convert a value to JavaScript through JSON.
+pub(crate) fn to_js<T: Serialize + ?Sized>(val: &T) -> io::Result<JsValue> {
+    let json = serde_json::to_string(val)
+        .map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err))?;
+    js_sys::JSON::parse(&json).err_kind(io::ErrorKind::InvalidData)
+}
+
+/// Convert a value from JavaScript through JSON.
+pub(crate) fn from_js<T: DeserializeOwned>(val: &JsValue) -> io::Result<T> {
+    let json: String = js_sys::JSON::stringify(val)
+        .err_kind(io::ErrorKind::InvalidData)?
+        .into();
+    serde_json::from_str(&json).map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err))
+}
+
+/// Collect a `DOMStringList`.
+fn strings(list: &web_sys::DomStringList) -> Vec<String> {
+    (0..list.length()).filter_map(|i| list.item(i)).collect()
+}
@@ -0,0 +1,52 @@
+use crate::fs::Storage;
+use std::cell::RefCell;
+use std::collections::BTreeMap;
+use std::io;
+
+/// A transient in-memory storage object.
+///
+/// `MemoryStorage` behaves like `LocalStorage`, except that its contents are
+/// lost when it's dropped. This is useful for tests, and as a stand-in when
+/// persistent storage is unavailable.
+#[derive(Debug, Default)]
+pub struct MemoryStorage {
+    map: RefCell<BTreeMap<String, String>>,
+}
+
+impl MemoryStorage {
+    /// Create a new, empty instance.
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl Storage for MemoryStorage {
+    fn get(&self, key: &str) -> Option<String> {
+        self.map.borrow().get(key).cloned()
+    }
+
+    fn insert(&self, key: &str, val: &str) -> io::Result<()> {
+        self.map.borrow_mut().insert(key.to_owned(), val.to_owned());
+        Ok(())
+    }
+
+    fn remove(&self, key: &str) -> Option<String> {
+        self.map.borrow_mut().remove(key)
+    }
+
+    fn clear(&self) {
+        self.map.borrow_mut().clear()
+    }
+
+    fn len(&self) -> usize {
+        self.map.borrow().len()
+    }
+
+    fn key(&self, index: usize) -> Option<String> {
+        self.map.borrow().keys().nth(index).cloned()
+    }
+
+    fn keys(&self) -> Vec<String> {
+        self.map.borrow().keys().cloned().collect()
+    }
+}
@@ -0,0 +1,64 @@
+use std::time::Duration;
+
+/// Metadata about a file or directory.
+///
+/// This `struct` is created by the [`metadata`] function, and the
+/// [`metadata`](crate::fs::DirEntry::metadata) method on [`DirEntry`]. See their
+/// documentation for more.
+///
+/// [`metadata`]: crate::fs::metadata
+/// [`DirEntry`]: crate::fs::DirEntry
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Metadata {
+    is_dir: bool,
+    len: u64,
+    modified: Option<Duration>,
+}
+
+impl Metadata {
+    /// Create a new instance for a file.
+    pub(crate) fn file(file: &web_sys::File) -> Self {
+        Self {
+            is_dir: false,
+            len: file.size() as u64,
+            modified: Some(Duration::from_millis(file.last_modified() as u64)),
+        }
+    }
+
+    /// Create a new instance for a directory.
+    pub(crate) fn dir() -> Self {
+        Self {
+            is_dir: true,
+            len: 0,
+            modified: None,
+        }
+    }
+
+    /// Returns `true` if this metadata is for a directory.
+    pub fn is_dir(&self) -> bool {
+        self.is_dir
+    }
+
+    /// Returns `true` if this metadata is for a file.
+    pub fn is_file(&self) -> bool {
+        !self.is_dir
+    }
+
+    /// Returns the size of the file in bytes, or `0` for directories.
+    pub fn len(&self) -> u64 {
+        self.len
+    }
+
+    /// Returns true if the file is empty.
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Returns when the file was last modified, as the time since the Unix
+    /// epoch.
+    ///
+    /// This is `None` for directories.
+    pub fn modified(&self) -> Option<Duration> {
+        self.modified
+    }
+}
@@ -1,4 +1,45 @@
 //! Filesystem manipulation operations.
+#[macro_use]
+mod web_storage_type;
+
+mod cursor;
+mod file;
+mod file_system;
+mod indexed_db;
 mod local_storage;
+mod memory_storage;
+mod metadata;
+mod namespace;
+mod object_store;
+mod persisted;
+mod read_dir;
+mod schema;
+mod session_storage;
+mod storage;
+mod storage_change;
+mod storage_manager;
+mod transaction;
+mod web_storage;
 
+pub use cursor::Cursor;
+pub use file::{Chunks, File};
+pub use file_system::{
+    create_dir, create_dir_all, metadata, read, read_dir, read_to_string, remove_dir,
+    remove_dir_all, remove_file, rename, write,
+};
+pub use indexed_db::{IndexedDb, IndexedDbBuilder, Upgrade};
 pub use local_storage::LocalStorage;
+pub use memory_storage::MemoryStorage;
+pub use metadata::Metadata;
+pub use namespace::Namespace;
+pub use object_store::{Index, ObjectStore};
+pub use persisted::{Persisted, PersistedStream};
+pub use read_dir::{DirEntry, ReadDir};
+pub use schema::Schema;
+pub use session_storage::SessionStorage;
+pub use storage::{Drain, Iter, Storage};
+pub use storage_change::{StorageArea, StorageChange, StorageChanges};
+pub use storage_manager::{estimate, persist, persisted, StorageEstimate};
+pub use transaction::{Transaction, TransactionMode};
+
+use web_storage::WebStorage;
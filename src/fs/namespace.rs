@@ -0,0 +1,72 @@
+use crate::fs::Storage;
+use std::io;
+
+/// A view of a [`Storage`] which transparently prefixes all keys.
+///
+/// Keys outside of the namespace are not visible through the view, and are
+/// left untouched by [`clear`](Storage::clear). Namespaces can be nested.
+///
+/// This `struct` is created by the [`namespace`] method on [`Storage`]. See
+/// its documentation for more.
+///
+/// [`namespace`]: Storage::namespace
+#[derive(Debug)]
+pub struct Namespace<'a, S: ?Sized> {
+    storage: &'a S,
+    prefix: String,
+}
+
+impl<'a, S: Storage + ?Sized> Namespace<'a, S> {
+    /// Create a new instance.
+    pub(crate) fn new(storage: &'a S, prefix: &str) -> Self {
+        Self {
+            storage,
+            prefix: prefix.to_owned(),
+        }
+    }
+
+    /// Returns the prefix of the namespace.
+    pub fn prefix(&self) -> &str {
+        &self.prefix
+    }
+
+    fn prefixed(&self, key: &str) -> String {
+        format!("{}{}", self.prefix, key)
+    }
+}
+
+impl<S: Storage + ?Sized> Storage for Namespace<'_, S> {
+    fn get(&self, key: &str) -> Option<String> {
+        self.storage.get(&self.prefixed(key))
+    }
+
+    fn insert(&self, key: &str, val: &str) -> io::Result<()> {
+        self.storage.insert(&self.prefixed(key), val)
+    }
+
+    fn remove(&self, key: &str) -> Option<String> {
+        self.storage.remove(&self.prefixed(key))
+    }
+
+    fn clear(&self) {
+        for key in self.keys() {
+            self.remove(&key);
+        }
+    }
+
+    fn len(&self) -> usize {
+        self.keys().len()
+    }
+
+    fn key(&self, index: usize) -> Option<String> {
+        self.keys().into_iter().nth(index)
+    }
+
+    fn keys(&self) -> Vec<String> {
+        self.storage
+            .keys()
+            .into_iter()
+            .filter_map(|key| key.strip_prefix(&self.prefix).map(str::to_owned))
+            .collect()
+    }
+}
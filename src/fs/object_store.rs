@@ -0,0 +1,259 @@
+use crate::fs::indexed_db::{from_js, request, to_js};
+use crate::fs::Cursor;
+use crate::utils::ResultExt;
+
+use serde::{de::DeserializeOwned, Serialize};
+
+use std::io;
+
+/// An object store in an [`IndexedDb`].
+///
+/// This `struct` is created by the [`object_store`] method on
+/// [`Transaction`], or by methods on [`Upgrade`]. See their documentation for
+/// more.
+///
+/// [`IndexedDb`]: crate::fs::IndexedDb
+/// [`object_store`]: crate::fs::Transaction::object_store
+/// [`Transaction`]: crate::fs::Transaction
+/// [`Upgrade`]: crate::fs::Upgrade
+#[derive(Debug)]
+pub struct ObjectStore {
+    inner: web_sys::IdbObjectStore,
+}
+
+impl ObjectStore {
+    /// Create a new instance.
+    pub(crate) fn new(inner: web_sys::IdbObjectStore) -> Self {
+        Self { inner }
+    }
+
+    /// Get the name of the object store.
+    pub fn name(&self) -> String {
+        self.inner.name()
+    }
+
+    /// Get the value corresponding to the key.
+    ///
+    /// # Errors
+    ///
+    /// An error of kind `io::ErrorKind::InvalidData` is returned if the value
+    /// could not be deserialized.
+    pub async fn get<K, V>(&self, key: &K) -> io::Result<Option<V>>
+    where
+        K: Serialize + ?Sized,
+        V: DeserializeOwned,
+    {
+        let req = self
+            .inner
+            .get(&to_js(key)?)
+            .dom_err_kind(io::ErrorKind::Other)?;
+        let val = request(&req).await?;
+        if val.is_undefined() {
+            return Ok(None);
+        }
+        from_js(&val).map(Some)
+    }
+
+    /// Get all values in the object store, ordered by key.
+    ///
+    /// # Errors
+    ///
+    /// An error of kind `io::ErrorKind::InvalidData` is returned if a value
+    /// could not be deserialized.
+    pub async fn get_all<V: DeserializeOwned>(&self) -> io::Result<Vec<V>> {
+        let req = self.inner.get_all().dom_err_kind(io::ErrorKind::Other)?;
+        from_js(&request(&req).await?)
+    }
+
+    /// Insert a value, replacing any value with the same key.
+    ///
+    /// The key is read from the value using the object store's key path, or
+    /// generated if the object store uses auto-incrementing keys.
+    ///
+    /// # Errors
+    ///
+    /// An error of kind `io::ErrorKind::InvalidInput` is returned if no key
+    /// could be found for the value, and an error of kind
+    /// `io::ErrorKind::PermissionDenied` if the transaction is read-only.
+    pub async fn put<V: Serialize + ?Sized>(&self, val: &V) -> io::Result<()> {
+        let req = self
+            .inner
+            .put(&to_js(val)?)
+            .dom_err_kind(io::ErrorKind::Other)?;
+        request(&req).await?;
+        Ok(())
+    }
+
+    /// Insert a value under a key, replacing any value with the same key.
+    ///
+    /// # Errors
+    ///
+    /// An error of kind `io::ErrorKind::InvalidInput` is returned if the object
+    /// store uses a key path.
+    pub async fn put_with_key<V, K>(&self, val: &V, key: &K) -> io::Result<()>
+    where
+        V: Serialize + ?Sized,
+        K: Serialize + ?Sized,
+    {
+        let req = self
+            .inner
+            .put_with_key(&to_js(val)?, &to_js(key)?)
+            .dom_err_kind(io::ErrorKind::Other)?;
+        request(&req).await?;
+        Ok(())
+    }
+
+    /// Remove the value corresponding to the key.
+    pub async fn delete<K: Serialize + ?Sized>(&self, key: &K) -> io::Result<()> {
+        let req = self
+            .inner
+            .delete(&to_js(key)?)
+            .dom_err_kind(io::ErrorKind::Other)?;
+        request(&req).await?;
+        Ok(())
+    }
+
+    /// Remove all values from the object store.
+    pub async fn clear(&self) -> io::Result<()> {
+        let req = self.inner.clear().dom_err_kind(io::ErrorKind::Other)?;
+        request(&req).await?;
+        Ok(())
+    }
+
+    /// Count the values in the object store.
+    pub async fn count(&self) -> io::Result<u32> {
+        let req = self.inner.count().dom_err_kind(io::ErrorKind::Other)?;
+        Ok(request(&req).await?.as_f64().unwrap_or(0.0) as u32)
+    }
+
+    /// Iterate over all keys and values in the object store, ordered by key.
+    pub fn cursor<K, V>(&self) -> io::Result<Cursor<K, V>>
+    where
+        K: DeserializeOwned,
+        V: DeserializeOwned,
+    {
+        let req = self
+            .inner
+            .open_cursor()
+            .dom_err_kind(io::ErrorKind::Other)?;
+        Ok(Cursor::new(req))
+    }
+
+    /// Create an index which looks up values by the field at `key_path`.
+    ///
+    /// This can only be called while the database is being upgraded.
+    ///
+    /// # Errors
+    ///
+    /// An error of kind `io::ErrorKind::AlreadyExists` is returned if an index
+    /// with the same name already exists.
+    pub fn create_index(&self, name: &str, key_path: &str, unique: bool) -> io::Result<Index> {
+        let params = web_sys::IdbIndexParameters::new();
+        params.set_unique(unique);
+        let index = self
+            .inner
+            .create_index_with_str_and_optional_parameters(name, key_path, &params)
+            .dom_err_kind(io::ErrorKind::Other)?;
+        Ok(Index { inner: index })
+    }
+
+    /// Delete an index.
+    ///
+    /// This can only be called while the database is being upgraded.
+    pub fn delete_index(&self, name: &str) -> io::Result<()> {
+        self.inner
+            .delete_index(name)
+            .dom_err_kind(io::ErrorKind::Other)
+    }
+
+    /// Access one of the object store's indices.
+    ///
+    /// # Errors
+    ///
+    /// An error of kind `io::ErrorKind::NotFound` is returned if the index
+    /// doesn't exist.
+    pub fn index(&self, name: &str) -> io::Result<Index> {
+        let index = self.inner.index(name).dom_err_kind(io::ErrorKind::Other)?;
+        Ok(Index { inner: index })
+    }
+}
+
+/// An index on an [`ObjectStore`].
+///
+/// This `struct` is created by the [`index`] and [`create_index`] methods on
+/// [`ObjectStore`]. See their documentation for more.
+///
+/// [`index`]: ObjectStore::index
+/// [`create_index`]: ObjectStore::create_index
+#[derive(Debug)]
+pub struct Index {
+    inner: web_sys::IdbIndex,
+}
+
+impl Index {
+    /// Get the name of the index.
+    pub fn name(&self) -> String {
+        self.inner.name()
+    }
+
+    /// Get the first value whose indexed field matches the key.
+    ///
+    /// # Errors
+    ///
+    /// An error of kind `io::ErrorKind::InvalidData` is returned if the value
+    /// could not be deserialized.
+    pub async fn get<K, V>(&self, key: &K) -> io::Result<Option<V>>
+    where
+        K: Serialize + ?Sized,
+        V: DeserializeOwned,
+    {
+        let req = self
+            .inner
+            .get(&to_js(key)?)
+            .dom_err_kind(io::ErrorKind::Other)?;
+        let val = request(&req).await?;
+        if val.is_undefined() {
+            return Ok(None);
+        }
+        from_js(&val).map(Some)
+    }
+
+    /// Get all values whose indexed field matches the key.
+    ///
+    /// # Errors
+    ///
+    /// An error of kind `io::ErrorKind::InvalidData` is returned if a value
+    /// could not be deserialized.
+    pub async fn get_all<K, V>(&self, key: &K) -> io::Result<Vec<V>>
+    where
+        K: Serialize + ?Sized,
+        V: DeserializeOwned,
+    {
+        let req = self
+            .inner
+            .get_all_with_key(&to_js(key)?)
+            .dom_err_kind(io::ErrorKind::Other)?;
+        from_js(&request(&req).await?)
+    }
+
+    /// Count the values in the index.
+    pub async fn count(&self) -> io::Result<u32> {
+        let req = self.inner.count().dom_err_kind(io::ErrorKind::Other)?;
+        Ok(request(&req).await?.as_f64().unwrap_or(0.0) as u32)
+    }
+
+    /// Iterate over all values in the index, ordered by their indexed field.
+    ///
+    /// The cursor yields the indexed field as the key.
+    pub fn cursor<K, V>(&self) -> io::Result<Cursor<K, V>>
+    where
+        K: DeserializeOwned,
+        V: DeserializeOwned,
+    {
+        let req = self
+            .inner
+            .open_cursor()
+            .dom_err_kind(io::ErrorKind::Other)?;
+        Ok(Cursor::new(req))
+    }
+}
@@ -0,0 +1,222 @@
+use crate::fs::{LocalStorage, StorageChanges};
+
+use async_channel::{self as channel, Receiver, Sender};
+use futures_core::Stream;
+use serde::{de::DeserializeOwned, Serialize};
+
+use std::cell::RefCell;
+use std::future::poll_fn;
+use std::io;
+use std::pin::Pin;
+use std::rc::Rc;
+use std::task::{Context, Poll};
+
+/// A value which is kept in [`LocalStorage`], and synchronized across tabs.
+///
+/// The value is loaded when the cell is created, and written through to
+/// storage every time it's set. When another tab changes the value, the cell
+/// updates itself. If another tab removes the value, the cell is reset to its
+/// default.
+///
+/// # Examples
+///
+/// ```no_run
+/// use localghost::prelude::*;
+/// use localghost::{fs, log};
+/// use async_std::prelude::*;
+/// use std::io;
+///
+/// #[localghost::main]
+/// async fn main() -> io::Result<()> {
+///     let count = fs::Persisted::new("count", 0_u32)?;
+///     count.update(|count| *count += 1)?;
+///     log::info!("this page was opened {} times", count.get());
+///
+///     let mut changes = count.changes();
+///     while let Some(count) = changes.next().await {
+///         log::info!("the count is now {}", count);
+///     }
+///     Ok(())
+/// }
+/// ```
+#[derive(Debug)]
+pub struct Persisted<T> {
+    storage: LocalStorage,
+    shared: Rc<RefCell<Shared<T>>>,
+    /// Stops applying changes made by other tabs when dropped.
+    _close: Sender<()>,
+}
+
+#[derive(Debug)]
+struct Shared<T> {
+    key: String,
+    value: T,
+    default: T,
+    subscribers: Vec<Sender<T>>,
+}
+
+impl<T: Clone> Shared<T> {
+    fn set(&mut self, value: T) {
+        self.value = value;
+        let value = &self.value;
+        self.subscribers
+            .retain(|sender| sender.try_send(value.clone()).is_ok());
+    }
+}
+
+impl<T> Persisted<T>
+where
+    T: Serialize + DeserializeOwned + Clone + 'static,
+{
+    /// Load the value stored under `key`, or use `default` if there is none.
+    ///
+    /// Stored values which can't be deserialized are replaced by the default.
+    ///
+    /// # Errors
+    ///
+    /// An error of kind `io::ErrorKind::PermissionDenied` is returned if
+    /// local Storage is unavailable.
+    pub fn new(key: &str, default: T) -> io::Result<Self> {
+        let storage = LocalStorage::open()?;
+        let value = match storage.get_json(key) {
+            Ok(Some(value)) => value,
+            Ok(None) => default.clone(),
+            Err(err) => {
+                crate::log::warn!("Persisted({}): {}", key, err);
+                default.clone()
+            }
+        };
+
+        let shared = Rc::new(RefCell::new(Shared {
+            key: key.to_owned(),
+            value,
+            default,
+            subscribers: vec![],
+        }));
+
+        let (close, closed) = channel::bounded(1);
+        crate::task::spawn_local(sync(shared.clone(), storage.changes(), closed));
+
+        Ok(Self {
+            storage,
+            shared,
+            _close: close,
+        })
+    }
+
+    /// Get the key the value is stored under.
+    pub fn key(&self) -> String {
+        self.shared.borrow().key.clone()
+    }
+
+    /// Get a copy of the current value.
+    pub fn get(&self) -> T {
+        self.shared.borrow().value.clone()
+    }
+
+    /// Access the current value without copying it.
+    pub fn with<F, R>(&self, f: F) -> R
+    where
+        F: FnOnce(&T) -> R,
+    {
+        f(&self.shared.borrow().value)
+    }
+
+    /// Set the value, and write it to storage.
+    ///
+    /// # Errors
+    ///
+    /// An error of kind `io::ErrorKind::StorageFull` is returned if the
+    /// storage is full, in which case the value is left unchanged.
+    pub fn set(&self, value: T) -> io::Result<()> {
+        let mut shared = self.shared.borrow_mut();
+        self.storage.insert_json(&shared.key, &value)?;
+        shared.set(value);
+        Ok(())
+    }
+
+    /// Modify the value in place, and write it to storage.
+    ///
+    /// # Errors
+    ///
+    /// An error of kind `io::ErrorKind::StorageFull` is returned if the
+    /// storage is full, in which case the value is left unchanged.
+    pub fn update<F>(&self, f: F) -> io::Result<()>
+    where
+        F: FnOnce(&mut T),
+    {
+        let mut value = self.get();
+        f(&mut value);
+        self.set(value)
+    }
+
+    /// Create a stream of value changes, made either through this instance
+    /// or by another tab.
+    pub fn changes(&self) -> PersistedStream<T> {
+        let (sender, receiver) = channel::unbounded();
+        self.shared.borrow_mut().subscribers.push(sender);
+        PersistedStream { receiver }
+    }
+}
+
+/// Apply the changes other tabs make to the value, until `closed` is closed.
+async fn sync<T>(
+    shared: Rc<RefCell<Shared<T>>>,
+    mut changes: StorageChanges,
+    mut closed: Receiver<()>,
+) where
+    T: DeserializeOwned + Clone,
+{
+    loop {
+        let change = poll_fn(|cx| {
+            if Pin::new(&mut closed).poll_next(cx).is_ready() {
+                return Poll::Ready(None);
+            }
+            Pin::new(&mut changes).poll_next(cx)
+        })
+        .await;
+        let change = match change {
+            Some(change) => change,
+            None => return,
+        };
+
+        let mut shared = shared.borrow_mut();
+        if change.key().is_some_and(|key| key != shared.key) {
+            continue;
+        }
+        match change.new_value() {
+            // The value was removed, or the storage was cleared.
+            None => {
+                let default = shared.default.clone();
+                shared.set(default);
+            }
+            Some(value) => match serde_json::from_str(value) {
+                Ok(value) => shared.set(value),
+                Err(err) => crate::log::warn!("Persisted({}): {}", shared.key, err),
+            },
+        }
+    }
+}
+
+/// A stream of [`Persisted`] value changes.
+///
+/// This `struct` is created by the [`changes`] method on [`Persisted`]. See
+/// its documentation for more.
+///
+/// [`changes`]: Persisted::changes
+#[pin_project::pin_project]
+#[derive(Debug)]
+#[must_use = "Streams do nothing unless polled"]
+pub struct PersistedStream<T> {
+    #[pin]
+    receiver: Receiver<T>,
+}
+
+impl<T> Stream for PersistedStream<T> {
+    type Item = T;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = self.project();
+        this.receiver.poll_next(cx)
+    }
+}
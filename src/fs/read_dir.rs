@@ -0,0 +1,116 @@
+use crate::fs::{file_system::call, Metadata};
+use crate::prelude::*;
+use crate::utils::ResultExt;
+
+use futures_core::{ready, Stream};
+use wasm_bindgen::JsCast;
+use wasm_bindgen_futures::JsFuture;
+use web_sys::{FileSystemHandle, FileSystemHandleKind};
+
+use std::fmt::{self, Debug};
+use std::future::Future;
+use std::io;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+
+/// A stream over the entries of a directory.
+///
+/// This `struct` is created by the [`read_dir`] function. See its
+/// documentation for more.
+///
+/// [`read_dir`]: crate::fs::read_dir
+#[must_use = "Streams do nothing unless polled"]
+pub struct ReadDir {
+    path: String,
+    iter: js_sys::AsyncIterator,
+    next: Option<JsFuture>,
+}
+
+impl ReadDir {
+    /// Create a new instance.
+    pub(crate) fn new(path: String, dir: web_sys::FileSystemDirectoryHandle) -> Self {
+        Self {
+            path,
+            iter: dir.values(),
+            next: None,
+        }
+    }
+}
+
+impl Debug for ReadDir {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ReadDir").field("path", &self.path).finish()
+    }
+}
+
+impl Stream for ReadDir {
+    type Item = io::Result<DirEntry>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        if self.next.is_none() {
+            let promise = self.iter.next().err_kind(io::ErrorKind::Other);
+            match promise {
+                Ok(promise) => self.next = Some(JsFuture::from(promise)),
+                Err(err) => return Poll::Ready(Some(Err(err))),
+            }
+        }
+
+        let res = ready!(Pin::new(self.next.as_mut().unwrap_throw()).poll(cx));
+        self.next = None;
+        let next: js_sys::IteratorNext = match res.dom_err_kind(io::ErrorKind::Other) {
+            Ok(next) => next.unchecked_into(),
+            Err(err) => return Poll::Ready(Some(Err(err))),
+        };
+        if next.done() {
+            return Poll::Ready(None);
+        }
+
+        let handle: FileSystemHandle = next.value().unchecked_into();
+        let path = match self.path.as_str() {
+            "" => handle.name(),
+            parent => format!("{}/{}", parent, handle.name()),
+        };
+        Poll::Ready(Some(Ok(DirEntry { path, handle })))
+    }
+}
+
+/// An entry in a directory.
+///
+/// This `struct` is yielded by [`ReadDir`]. See its documentation for more.
+#[derive(Debug)]
+pub struct DirEntry {
+    path: String,
+    handle: FileSystemHandle,
+}
+
+impl DirEntry {
+    /// Returns the full path of the entry.
+    pub fn path(&self) -> &str {
+        &self.path
+    }
+
+    /// Returns the name of the entry, without its parent directories.
+    pub fn file_name(&self) -> String {
+        self.handle.name()
+    }
+
+    /// Returns `true` if the entry is a directory.
+    pub fn is_dir(&self) -> bool {
+        self.handle.kind() == FileSystemHandleKind::Directory
+    }
+
+    /// Returns `true` if the entry is a file.
+    pub fn is_file(&self) -> bool {
+        self.handle.kind() == FileSystemHandleKind::File
+    }
+
+    /// Query the metadata of the entry.
+    pub async fn metadata(&self) -> io::Result<Metadata> {
+        if self.is_dir() {
+            return Ok(Metadata::dir());
+        }
+        let handle: &web_sys::FileSystemFileHandle = self.handle.unchecked_ref();
+        let file = call(handle.get_file(), io::ErrorKind::NotFound).await?;
+        Ok(Metadata::file(&file.unchecked_into()))
+    }
+}
@@ -0,0 +1,132 @@
+use serde_json::{json, Value};
+
+use std::collections::BTreeMap;
+use std::fmt::{self, Debug};
+use std::io;
+
+type Migration = Box<dyn Fn(Value) -> Value>;
+
+/// The key holding the version in the envelope of a stored value.
+const VERSION_KEY: &str = "$localghost_version";
+
+/// A versioned format for values kept in a [`Storage`].
+///
+/// Values written through a schema are stored together with its version.
+/// When a value written by an older version is read, the registered
+/// migrations are run in order to bring it up to date, and the result is
+/// written back. Values stored without a version, such as those written by
+/// [`insert_json`] before a schema was introduced, are treated as version
+/// `0`, so they need a migration from version `0` to be read.
+///
+/// [`Storage`]: crate::fs::Storage
+/// [`insert_json`]: crate::fs::Storage::insert_json
+///
+/// # Examples
+///
+/// ```no_run
+/// use localghost::prelude::*;
+/// use localghost::fs::{LocalStorage, Schema, Storage};
+/// use serde::{Deserialize, Serialize};
+/// use std::io;
+///
+/// #[derive(Serialize, Deserialize)]
+/// struct Settings {
+///     theme: String,
+///     font_size: u32,
+/// }
+///
+/// #[localghost::main]
+/// async fn main() -> io::Result<()> {
+///     // Settings stored before the schema existed didn't have a font size.
+///     let schema = Schema::new(1).migration(0, |mut value| {
+///         value["font_size"] = 14.into();
+///         value
+///     });
+///
+///     let storage = LocalStorage::open()?;
+///     let settings: Option<Settings> = storage.get_versioned("settings", &schema)?;
+///     Ok(())
+/// }
+/// ```
+pub struct Schema {
+    version: u32,
+    migrations: BTreeMap<u32, Migration>,
+}
+
+impl Schema {
+    /// Create a new schema at the given version.
+    pub fn new(version: u32) -> Self {
+        Self {
+            version,
+            migrations: BTreeMap::new(),
+        }
+    }
+
+    /// Register a function which upgrades a value from version `from` to
+    /// version `from + 1`.
+    pub fn migration<F>(mut self, from: u32, f: F) -> Self
+    where
+        F: Fn(Value) -> Value + 'static,
+    {
+        self.migrations.insert(from, Box::new(f));
+        self
+    }
+
+    /// Get the current version of the schema.
+    pub fn version(&self) -> u32 {
+        self.version
+    }
+
+    /// Wrap a value in an envelope holding the current version.
+    pub(crate) fn encode(&self, data: Value) -> Value {
+        json!({ VERSION_KEY: self.version, "data": data })
+    }
+
+    /// Unwrap a stored value, and migrate it to the current version.
+    ///
+    /// Returns whether any migrations were run.
+    pub(crate) fn decode(&self, value: Value) -> io::Result<(Value, bool)> {
+        let (mut version, mut data) = match value {
+            Value::Object(mut map) if map.len() == 2 && map.contains_key("data") => {
+                let version = map.get(VERSION_KEY).and_then(Value::as_u64);
+                match version {
+                    Some(version) => (version as u32, map.remove("data").unwrap()),
+                    None => (0, Value::Object(map)),
+                }
+            }
+            value => (0, value),
+        };
+
+        if version > self.version {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!(
+                    "Schema: stored version {} is newer than {}",
+                    version, self.version
+                ),
+            ));
+        }
+
+        let migrated = version < self.version;
+        while version < self.version {
+            let migration = self.migrations.get(&version).ok_or_else(|| {
+                io::Error::new(
+                    io::ErrorKind::InvalidData,
+                    format!("Schema: no migration from version {}", version),
+                )
+            })?;
+            data = migration(data);
+            version += 1;
+        }
+        Ok((data, migrated))
+    }
+}
+
+impl Debug for Schema {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Schema")
+            .field("version", &self.version)
+            .field("migrations", &self.migrations.keys().collect::<Vec<_>>())
+            .finish()
+    }
+}
@@ -0,0 +1,15 @@
+web_storage!(
+    /// Access a storage object for the Document's origin which is cleared when
+    /// the page session ends.
+    ///
+    /// Unlike `LocalStorage`, every tab has its own session Storage.
+    ///
+    /// [Read more](https://developer.mozilla.org/en-US/docs/Web/API/Window/sessionStorage)
+    SessionStorage,
+    "session",
+    session_storage,
+    Session,
+    changes:
+    /// Returns a stream of changes made to the session Storage by another
+    /// document in the same tab, such as an iframe.
+);
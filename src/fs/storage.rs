@@ -0,0 +1,232 @@
+use crate::fs::{Namespace, Schema};
+use serde::{de::DeserializeOwned, Serialize};
+use std::{io, vec};
+
+/// A key-value store for string data.
+///
+/// This trait is implemented by [`LocalStorage`], [`SessionStorage`] and
+/// [`MemoryStorage`], so code written against it can be used with any of them.
+///
+/// [`LocalStorage`]: crate::fs::LocalStorage
+/// [`SessionStorage`]: crate::fs::SessionStorage
+/// [`MemoryStorage`]: crate::fs::MemoryStorage
+///
+/// # Examples
+///
+/// ```no_run
+/// use localghost::prelude::*;
+/// use localghost::fs::{LocalStorage, MemoryStorage, Storage};
+/// use std::io;
+///
+/// fn visit(storage: &dyn Storage) -> io::Result<()> {
+///     let visits: u32 = storage.get("visits").and_then(|s| s.parse().ok()).unwrap_or(0);
+///     storage.insert("visits", &(visits + 1).to_string())
+/// }
+///
+/// #[localghost::main]
+/// async fn main() -> io::Result<()> {
+///     visit(&LocalStorage::open()?)?;
+///     visit(&MemoryStorage::new())?;
+///     Ok(())
+/// }
+/// ```
+pub trait Storage {
+    /// Returns the value corresponding to the key.
+    fn get(&self, key: &str) -> Option<String>;
+
+    /// Inserts a key-value pair into the Storage.
+    ///
+    /// # Errors
+    ///
+    /// An error of kind `io::ErrorKind::StorageFull` is returned if the
+    /// storage is full.
+    fn insert(&self, key: &str, val: &str) -> io::Result<()>;
+
+    /// Removes a key from the Storage, returning its value if it was present.
+    fn remove(&self, key: &str) -> Option<String>;
+
+    /// Clears the Storage, removing all key-value pairs.
+    fn clear(&self);
+
+    /// Returns the number of elements in the Storage.
+    fn len(&self) -> usize;
+
+    /// Returns true if the Storage contains no elements.
+    fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns the name of the key at an index, in an implementation-defined
+    /// order.
+    fn key(&self, index: usize) -> Option<String>;
+
+    /// Returns all keys in the Storage.
+    fn keys(&self) -> Vec<String> {
+        (0..self.len()).filter_map(|i| self.key(i)).collect()
+    }
+
+    /// Returns an iterator over all key-value pairs in the Storage.
+    ///
+    /// The keys are read when the iterator is created, so the Storage may be
+    /// modified while iterating. Entries which are removed before they're
+    /// reached are skipped.
+    fn iter(&self) -> Iter<'_, Self>
+    where
+        Self: Sized,
+    {
+        Iter {
+            storage: self,
+            keys: self.keys().into_iter(),
+        }
+    }
+
+    /// Removes all key-value pairs from the Storage, and returns them as an
+    /// iterator.
+    fn drain(&self) -> Drain {
+        let entries: Vec<_> = self
+            .keys()
+            .into_iter()
+            .filter_map(|key| self.get(&key).map(|val| (key, val)))
+            .collect();
+        self.clear();
+        Drain {
+            entries: entries.into_iter(),
+        }
+    }
+
+    /// Returns the value corresponding to the key, deserialized from JSON.
+    ///
+    /// # Errors
+    ///
+    /// An error of kind `io::ErrorKind::InvalidData` is returned if the value
+    /// could not be deserialized.
+    fn get_json<T>(&self, key: &str) -> io::Result<Option<T>>
+    where
+        Self: Sized,
+        T: DeserializeOwned,
+    {
+        match self.get(key) {
+            Some(json) => serde_json::from_str(&json).map(Some).map_err(invalid_data),
+            None => Ok(None),
+        }
+    }
+
+    /// Serializes a value as JSON, and inserts it into the Storage.
+    ///
+    /// # Errors
+    ///
+    /// An error of kind `io::ErrorKind::InvalidData` is returned if the value
+    /// could not be serialized. An error may also be returned if the storage
+    /// is full.
+    fn insert_json<T>(&self, key: &str, val: &T) -> io::Result<()>
+    where
+        Self: Sized,
+        T: Serialize + ?Sized,
+    {
+        let json = serde_json::to_string(val).map_err(invalid_data)?;
+        self.insert(key, &json)
+    }
+
+    /// Returns the value corresponding to the key, migrated to the current
+    /// version of the schema and deserialized from JSON.
+    ///
+    /// If any migrations were run, the upgraded value is written back.
+    ///
+    /// # Errors
+    ///
+    /// An error of kind `io::ErrorKind::InvalidData` is returned if the value
+    /// could not be deserialized, if it was stored by a newer version of the
+    /// schema, or if a migration is missing.
+    fn get_versioned<T>(&self, key: &str, schema: &Schema) -> io::Result<Option<T>>
+    where
+        Self: Sized,
+        T: DeserializeOwned,
+    {
+        let value = match self.get_json(key)? {
+            Some(value) => value,
+            None => return Ok(None),
+        };
+        let (data, migrated) = schema.decode(value)?;
+        if migrated {
+            self.insert_json(key, &schema.encode(data.clone()))?;
+        }
+        serde_json::from_value(data).map(Some).map_err(invalid_data)
+    }
+
+    /// Serializes a value as JSON tagged with the current version of the
+    /// schema, and inserts it into the Storage.
+    ///
+    /// # Errors
+    ///
+    /// An error of kind `io::ErrorKind::InvalidData` is returned if the value
+    /// could not be serialized. An error may also be returned if the storage
+    /// is full.
+    fn insert_versioned<T>(&self, key: &str, val: &T, schema: &Schema) -> io::Result<()>
+    where
+        Self: Sized,
+        T: Serialize + ?Sized,
+    {
+        let data = serde_json::to_value(val).map_err(invalid_data)?;
+        self.insert_json(key, &schema.encode(data))
+    }
+
+    /// Returns a view of the Storage which transparently prefixes all keys.
+    fn namespace(&self, prefix: &str) -> Namespace<'_, Self>
+    where
+        Self: Sized,
+    {
+        Namespace::new(self, prefix)
+    }
+}
+
+fn invalid_data(err: serde_json::Error) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, err)
+}
+
+/// An iterator over the entries of a [`Storage`].
+///
+/// This `struct` is created by the [`iter`] method on [`Storage`]. See its
+/// documentation for more.
+///
+/// [`iter`]: Storage::iter
+#[derive(Debug)]
+pub struct Iter<'a, S: ?Sized> {
+    storage: &'a S,
+    keys: vec::IntoIter<String>,
+}
+
+impl<S: Storage + ?Sized> Iterator for Iter<'_, S> {
+    type Item = (String, String);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            let key = self.keys.next()?;
+            if let Some(val) = self.storage.get(&key) {
+                return Some((key, val));
+            }
+        }
+    }
+}
+
+/// A draining iterator over the entries of a [`Storage`].
+///
+/// This `struct` is created by the [`drain`] method on [`Storage`]. See its
+/// documentation for more.
+///
+/// [`drain`]: Storage::drain
+#[derive(Debug)]
+pub struct Drain {
+    entries: vec::IntoIter<(String, String)>,
+}
+
+impl Iterator for Drain {
+    type Item = (String, String);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.entries.next()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.entries.size_hint()
+    }
+}
@@ -0,0 +1,124 @@
+use crate::events::EventStream;
+use crate::prelude::*;
+
+use futures_core::{ready, Stream};
+use wasm_bindgen::JsCast;
+
+use std::pin::Pin;
+use std::task::{Context, Poll};
+
+/// The kind of storage a [`StorageChange`] was made to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum StorageArea {
+    /// The change was made to [`LocalStorage`](crate::fs::LocalStorage).
+    Local,
+    /// The change was made to [`SessionStorage`](crate::fs::SessionStorage).
+    Session,
+}
+
+/// A change made to a storage object by another document.
+///
+/// [Read more](https://developer.mozilla.org/en-US/docs/Web/API/StorageEvent)
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StorageChange {
+    key: Option<String>,
+    old_value: Option<String>,
+    new_value: Option<String>,
+    url: String,
+    area: StorageArea,
+}
+
+impl StorageChange {
+    /// The key which was changed, or `None` if the storage was cleared.
+    pub fn key(&self) -> Option<&str> {
+        self.key.as_deref()
+    }
+
+    /// The value of the key before the change, or `None` if it was just added.
+    pub fn old_value(&self) -> Option<&str> {
+        self.old_value.as_deref()
+    }
+
+    /// The value of the key after the change, or `None` if it was removed.
+    pub fn new_value(&self) -> Option<&str> {
+        self.new_value.as_deref()
+    }
+
+    /// The url of the document which made the change.
+    pub fn url(&self) -> &str {
+        &self.url
+    }
+
+    /// The kind of storage which was changed.
+    pub fn area(&self) -> StorageArea {
+        self.area
+    }
+
+    /// Returns `true` if the change cleared the storage.
+    pub fn is_clear(&self) -> bool {
+        self.key.is_none()
+    }
+}
+
+/// A stream of [`StorageChange`]s.
+///
+/// This `struct` is created by the [`changes`] method on [`LocalStorage`] and
+/// [`SessionStorage`]. See their documentation for more.
+///
+/// [`changes`]: crate::fs::LocalStorage::changes
+/// [`LocalStorage`]: crate::fs::LocalStorage
+/// [`SessionStorage`]: crate::fs::SessionStorage
+#[pin_project::pin_project]
+#[derive(Debug)]
+#[must_use = "Streams do nothing unless polled"]
+pub struct StorageChanges {
+    #[pin]
+    events: EventStream,
+    /// `None` if the storage is kept in memory, and can't be changed by other
+    /// documents.
+    storage: Option<web_sys::Storage>,
+    area: StorageArea,
+}
+
+impl StorageChanges {
+    /// Create a new instance.
+    pub(crate) fn new(storage: Option<web_sys::Storage>, area: StorageArea) -> Self {
+        Self {
+            events: crate::utils::window().on("storage"),
+            storage,
+            area,
+        }
+    }
+}
+
+impl Stream for StorageChanges {
+    type Item = StorageChange;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let mut this = self.project();
+        loop {
+            let ev = match ready!(this.events.as_mut().poll_next(cx)) {
+                Some(ev) => ev,
+                None => return Poll::Ready(None),
+            };
+            let ev = ev
+                .into_raw()
+                .dyn_into::<web_sys::StorageEvent>()
+                .unwrap_throw();
+
+            // Both local and session storage dispatch on the window.
+            match (&ev.storage_area(), &*this.storage) {
+                (Some(area), Some(storage)) if area == storage => {}
+                _ => continue,
+            }
+
+            return Poll::Ready(Some(StorageChange {
+                key: ev.key(),
+                old_value: ev.old_value(),
+                new_value: ev.new_value(),
+                url: ev.url().unwrap_or_default(),
+                area: *this.area,
+            }));
+        }
+    }
+}
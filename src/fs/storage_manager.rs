@@ -0,0 +1,112 @@
+use crate::prelude::*;
+use crate::utils::{window, ResultExt};
+
+use wasm_bindgen::JsCast;
+use wasm_bindgen_futures::JsFuture;
+
+use std::io;
+
+/// An estimate of how much storage the origin uses, and how much it may use.
+///
+/// This `struct` is created by the [`estimate`] function. See its
+/// documentation for more.
+///
+/// [Read more](https://developer.mozilla.org/en-US/docs/Web/API/StorageManager/estimate)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct StorageEstimate {
+    usage: u64,
+    quota: u64,
+}
+
+impl StorageEstimate {
+    /// The approximate number of bytes used by the origin.
+    pub fn usage(&self) -> u64 {
+        self.usage
+    }
+
+    /// The approximate number of bytes available to the origin.
+    pub fn quota(&self) -> u64 {
+        self.quota
+    }
+}
+
+/// Estimate how much storage the origin uses, and how much it may use.
+///
+/// # Errors
+///
+/// An error of kind `io::ErrorKind::PermissionDenied` is returned if storage
+/// can't be managed, such as when the Document isn't served from a secure
+/// context.
+///
+/// # Examples
+///
+/// ```no_run
+/// use localghost::prelude::*;
+/// use localghost::{fs, log};
+/// use std::io;
+///
+/// #[localghost::main]
+/// async fn main() -> io::Result<()> {
+///     let estimate = fs::estimate().await?;
+///     log::info!("using {} of {} bytes", estimate.usage(), estimate.quota());
+///     Ok(())
+/// }
+/// ```
+pub async fn estimate() -> io::Result<StorageEstimate> {
+    let promise = manager()?.estimate().err_kind(io::ErrorKind::Other)?;
+    let estimate = JsFuture::from(promise)
+        .await
+        .err_kind(io::ErrorKind::Other)?;
+    let estimate: web_sys::StorageEstimate = estimate.unchecked_into();
+    Ok(StorageEstimate {
+        usage: estimate.get_usage().unwrap_or(0.0) as u64,
+        quota: estimate.get_quota().unwrap_or(0.0) as u64,
+    })
+}
+
+/// Request that the origin's storage is not cleared under storage pressure.
+///
+/// Returns `true` if the request was granted.
+///
+/// [Read more](https://developer.mozilla.org/en-US/docs/Web/API/StorageManager/persist)
+///
+/// # Errors
+///
+/// An error of kind `io::ErrorKind::PermissionDenied` is returned if storage
+/// can't be managed, such as when the Document isn't served from a secure
+/// context.
+pub async fn persist() -> io::Result<bool> {
+    let promise = manager()?.persist().err_kind(io::ErrorKind::Other)?;
+    let granted = JsFuture::from(promise)
+        .await
+        .err_kind(io::ErrorKind::Other)?;
+    Ok(granted.as_bool().unwrap_or(false))
+}
+
+/// Check whether the origin's storage is protected from being cleared under
+/// storage pressure.
+///
+/// # Errors
+///
+/// An error of kind `io::ErrorKind::PermissionDenied` is returned if storage
+/// can't be managed, such as when the Document isn't served from a secure
+/// context.
+pub async fn persisted() -> io::Result<bool> {
+    let promise = manager()?.persisted().err_kind(io::ErrorKind::Other)?;
+    let persisted = JsFuture::from(promise)
+        .await
+        .err_kind(io::ErrorKind::Other)?;
+    Ok(persisted.as_bool().unwrap_or(false))
+}
+
+/// Get the `StorageManager`, which is only available in secure contexts.
+pub(crate) fn manager() -> io::Result<web_sys::StorageManager> {
+    let manager = window().navigator().storage();
+    if manager.is_undefined() {
+        return Err(io::Error::new(
+            io::ErrorKind::PermissionDenied,
+            "Unable to access StorageManager.",
+        ));
+    }
+    Ok(manager)
+}
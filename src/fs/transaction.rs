@@ -0,0 +1,114 @@
+use crate::events::EventListener;
+use crate::fs::{indexed_db::dom_error, ObjectStore};
+use crate::utils::ResultExt;
+
+use async_channel::{bounded, Receiver};
+
+use std::io;
+
+/// The mode of a [`Transaction`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum TransactionMode {
+    /// Values can only be read.
+    ReadOnly,
+    /// Values can be read and written.
+    ReadWrite,
+}
+
+impl TransactionMode {
+    pub(crate) fn into_raw(self) -> web_sys::IdbTransactionMode {
+        match self {
+            TransactionMode::ReadOnly => web_sys::IdbTransactionMode::Readonly,
+            TransactionMode::ReadWrite => web_sys::IdbTransactionMode::Readwrite,
+        }
+    }
+}
+
+/// A transaction on one or more object stores of an [`IndexedDb`].
+///
+/// A transaction commits automatically once no more requests are made on
+/// it. Awaiting anything other than its requests, such as a timer or a
+/// network request, lets the transaction commit early, and further requests
+/// will fail.
+///
+/// This `struct` is created by the [`transaction`] method on [`IndexedDb`].
+/// See its documentation for more.
+///
+/// [`IndexedDb`]: crate::fs::IndexedDb
+/// [`transaction`]: crate::fs::IndexedDb::transaction
+#[derive(Debug)]
+pub struct Transaction {
+    inner: web_sys::IdbTransaction,
+    _listeners: Vec<EventListener>,
+    done: Receiver<bool>,
+}
+
+impl Transaction {
+    /// Create a new instance.
+    pub(crate) fn new(inner: web_sys::IdbTransaction) -> Self {
+        let (sender, done) = bounded(1);
+        let sender2 = sender.clone();
+        let complete = EventListener::listen(&inner, "complete", move |_| {
+            let _ = sender.try_send(true);
+        });
+        let abort = EventListener::listen(&inner, "abort", move |_| {
+            let _ = sender2.try_send(false);
+        });
+        Self {
+            inner,
+            _listeners: vec![complete, abort],
+            done,
+        }
+    }
+
+    /// Access one of the object stores in the transaction's scope.
+    ///
+    /// # Errors
+    ///
+    /// An error of kind `io::ErrorKind::NotFound` is returned if the object
+    /// store is not in scope.
+    pub fn object_store(&self, name: &str) -> io::Result<ObjectStore> {
+        let store = self
+            .inner
+            .object_store(name)
+            .err_kind(io::ErrorKind::NotFound)?;
+        Ok(ObjectStore::new(store))
+    }
+
+    /// Commit the transaction without waiting for it to finish on its own, and
+    /// wait until all changes are written.
+    ///
+    /// # Errors
+    ///
+    /// An error is returned if the transaction was aborted, such as when one of
+    /// its requests failed.
+    pub async fn commit(self) -> io::Result<()> {
+        // Not all browsers support committing explicitly, which is why
+        // `web-sys` marks it as deprecated.
+        #[allow(deprecated)]
+        let _ = self.inner.commit();
+        self.done().await
+    }
+
+    /// Wait until the transaction has finished, and all changes are written.
+    ///
+    /// # Errors
+    ///
+    /// An error is returned if the transaction was aborted, such as when one of
+    /// its requests failed.
+    pub async fn done(self) -> io::Result<()> {
+        match self.done.recv().await {
+            Ok(true) => Ok(()),
+            _ => Err(dom_error(self.inner.error())),
+        }
+    }
+
+    /// Abort the transaction, and roll back all of its changes.
+    ///
+    /// # Errors
+    ///
+    /// An error is returned if the transaction has already finished.
+    pub fn abort(self) -> io::Result<()> {
+        self.inner.abort().err_kind(io::ErrorKind::Other)
+    }
+}
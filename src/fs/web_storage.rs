@@ -0,0 +1,143 @@
+use crate::fs::{MemoryStorage, Storage, StorageArea, StorageChanges};
+use crate::prelude::*;
+use crate::utils::{window, ResultExt};
+use std::io;
+use wasm_bindgen::JsCast;
+
+/// The key written to check whether a storage object can be used.
+const PROBE_KEY: &str = "localghost:probe";
+
+/// The shared implementation of `LocalStorage` and `SessionStorage`.
+#[derive(Debug)]
+pub(crate) enum WebStorage {
+    /// A storage object provided by the browser.
+    Web(web_sys::Storage),
+    /// A transient fallback, used when the browser's storage is unavailable.
+    Memory(MemoryStorage),
+}
+
+impl WebStorage {
+    /// Create a new instance.
+    pub(crate) fn new(storage: web_sys::Storage) -> Self {
+        WebStorage::Web(storage)
+    }
+
+    /// Create a new instance which keeps its contents in memory.
+    pub(crate) fn memory() -> Self {
+        WebStorage::Memory(MemoryStorage::new())
+    }
+
+    /// Returns true if the contents outlive the instance.
+    pub(crate) fn is_persistent(&self) -> bool {
+        matches!(self, WebStorage::Web(_))
+    }
+
+    /// Check whether the storage has a quota, so that values can be written.
+    ///
+    /// Some browsers expose a storage object with a quota of zero in private
+    /// browsing mode, which fails every write. A store which already holds
+    /// values has a quota, even if it's full.
+    pub(crate) fn is_usable(&self) -> bool {
+        match self {
+            WebStorage::Web(_) if self.len() == 0 => has_quota(),
+            _ => true,
+        }
+    }
+
+    pub(crate) fn len(&self) -> usize {
+        match self {
+            WebStorage::Web(storage) => storage.length().unwrap_throw() as usize,
+            WebStorage::Memory(storage) => storage.len(),
+        }
+    }
+
+    pub(crate) fn clear(&self) {
+        match self {
+            WebStorage::Web(storage) => storage.clear().unwrap_throw(),
+            WebStorage::Memory(storage) => storage.clear(),
+        }
+    }
+
+    pub(crate) fn insert(&self, key: &str, val: &str) -> io::Result<()> {
+        match self {
+            WebStorage::Web(storage) => match storage.set_item(key, val) {
+                Ok(()) => Ok(()),
+                Err(err) if is_quota_exceeded(&err) => {
+                    Err(err).err_kind(io::ErrorKind::StorageFull)
+                }
+                Err(err) => Err(err).err_kind(io::ErrorKind::Other),
+            },
+            WebStorage::Memory(storage) => storage.insert(key, val),
+        }
+    }
+
+    pub(crate) fn remove(&self, key: &str) -> Option<String> {
+        match self {
+            WebStorage::Web(storage) => {
+                let val = self.get(key);
+                storage.remove_item(key).unwrap_throw();
+                val
+            }
+            WebStorage::Memory(storage) => storage.remove(key),
+        }
+    }
+
+    pub(crate) fn get(&self, key: &str) -> Option<String> {
+        match self {
+            WebStorage::Web(storage) => storage.get_item(key).ok().flatten(),
+            WebStorage::Memory(storage) => storage.get(key),
+        }
+    }
+
+    /// Get the key at an index.
+    pub(crate) fn key(&self, index: usize) -> Option<String> {
+        match self {
+            WebStorage::Web(storage) => storage.key(index as u32).ok().flatten(),
+            WebStorage::Memory(storage) => storage.key(index),
+        }
+    }
+
+    pub(crate) fn keys(&self) -> Vec<String> {
+        (0..self.len()).filter_map(|i| self.key(i)).collect()
+    }
+
+    pub(crate) fn changes(&self, area: StorageArea) -> StorageChanges {
+        let storage = match self {
+            WebStorage::Web(storage) => Some(storage.clone()),
+            WebStorage::Memory(_) => None,
+        };
+        StorageChanges::new(storage, area)
+    }
+}
+
+/// Check whether empty web storage has a quota, by writing a probe value.
+///
+/// The probe is written to session storage, which is restricted the same way
+/// in private browsing modes, but unlike local storage doesn't fire `storage`
+/// events in other tabs. If session storage can't be accessed at all, we
+/// can't tell, and assume there is a quota.
+fn has_quota() -> bool {
+    let storage = match window().session_storage() {
+        Ok(Some(storage)) => storage,
+        _ => return true,
+    };
+    match storage.set_item(PROBE_KEY, "") {
+        Ok(()) => {
+            let _ = storage.remove_item(PROBE_KEY);
+            true
+        }
+        Err(err) => !is_quota_exceeded(&err) || storage.length().unwrap_or(0) > 0,
+    }
+}
+
+/// Check whether an exception was thrown because the storage is full.
+fn is_quota_exceeded(err: &JsValue) -> bool {
+    match err.dyn_ref::<web_sys::DomException>() {
+        // Firefox used a different name before version 71.
+        Some(err) => matches!(
+            err.name().as_str(),
+            "QuotaExceededError" | "NS_ERROR_DOM_QUOTA_REACHED"
+        ),
+        None => false,
+    }
+}
@@ -0,0 +1,272 @@
+/// Define a wrapper around one of the browser's [`Storage`] objects.
+///
+/// `LocalStorage` and `SessionStorage` only differ in which storage object
+/// they open, so both are defined through this macro to keep them in sync.
+/// The first block of attributes documents the type, and the block after
+/// `changes:` documents its `changes` method.
+///
+/// [`Storage`]: crate::fs::Storage
+macro_rules! web_storage {
+    (
+        $(#[$attr:meta])*
+        $name:ident, $kind:literal, $open:ident, $area:ident,
+        changes: $(#[$changes_attr:meta])*
+    ) => {
+        $(#[$attr])*
+        #[derive(Debug)]
+        pub struct $name {
+            storage: crate::fs::WebStorage,
+        }
+
+        impl $name {
+            #[doc = concat!("Access the ", $kind, " Storage object for a Document's origin.")]
+            ///
+            /// # Errors
+            ///
+            /// This function will return an error if a request violates a policy decision, or the origin
+            /// is not a valid scheme/host/port tuple.
+            pub fn open() -> std::io::Result<Self> {
+                match crate::utils::window().$open() {
+                    Ok(Some(storage)) => Ok(Self {
+                        storage: crate::fs::WebStorage::new(storage),
+                    }),
+                    _ => Err(std::io::Error::new(
+                        std::io::ErrorKind::PermissionDenied,
+                        concat!("Unable to access ", $kind, " Storage object."),
+                    )),
+                }
+            }
+
+            #[doc = concat!("Access the ", $kind, " Storage object for a Document's origin, or fall back to")]
+            /// a transient in-memory store if it's unavailable.
+            ///
+            /// This keeps an application working in environments where persistent
+            /// storage is disabled, such as some private browsing modes. Use
+            /// [`is_persistent`](#method.is_persistent) to find out which store is
+            /// used. A store which is merely full is still used, and its writes fail
+            /// with `io::ErrorKind::StorageFull`.
+            pub fn open_with_fallback() -> Self {
+                match Self::open() {
+                    Ok(storage) if storage.storage.is_usable() => storage,
+                    _ => {
+                        crate::log::warn!(
+                            "{}: unavailable, falling back to memory",
+                            stringify!($name)
+                        );
+                        Self {
+                            storage: crate::fs::WebStorage::memory(),
+                        }
+                    }
+                }
+            }
+
+            /// Returns false if the contents are kept in memory, and will be lost when
+            /// the instance is dropped.
+            pub fn is_persistent(&self) -> bool {
+                self.storage.is_persistent()
+            }
+
+            #[doc = concat!("Returns the number of elements in ", $kind, " Storage.")]
+            pub fn len(&self) -> usize {
+                self.storage.len()
+            }
+
+            /// Returns true if the Storage contains no elements.
+            pub fn is_empty(&self) -> bool {
+                self.len() == 0
+            }
+
+            #[doc = concat!("Clears ", $kind, " Storage, removing all key-value pairs.")]
+            pub fn clear(&self) {
+                self.storage.clear()
+            }
+
+            #[doc = concat!("Inserts a key-value pair into the ", $kind, " Storage.")]
+            ///
+            /// # Errors
+            ///
+            /// An error of kind `io::ErrorKind::StorageFull` is returned if the
+            /// storage is full.
+            pub fn insert(&self, key: &str, val: &str) -> std::io::Result<()> {
+                self.storage.insert(key, val)
+            }
+
+            #[doc = concat!("Removes a key from the ", $kind, " Storage.")]
+            pub fn remove(&self, key: &str) -> Option<String> {
+                self.storage.remove(key)
+            }
+
+            /// Returns a reference to the value corresponding to the key.
+            pub fn get(&self, key: &str) -> Option<String> {
+                self.storage.get(key)
+            }
+
+            /// Returns the value corresponding to the key, deserialized from JSON.
+            ///
+            /// # Errors
+            ///
+            /// An error of kind `io::ErrorKind::InvalidData` is returned if the value
+            /// could not be deserialized.
+            pub fn get_json<T: serde::de::DeserializeOwned>(
+                &self,
+                key: &str,
+            ) -> std::io::Result<Option<T>> {
+                crate::fs::Storage::get_json(self, key)
+            }
+
+            #[doc = concat!("Serializes a value as JSON, and inserts it into the ", $kind, " Storage.")]
+            ///
+            /// # Errors
+            ///
+            /// An error of kind `io::ErrorKind::InvalidData` is returned if the value
+            /// could not be serialized, and an error may be returned if the storage
+            /// is full.
+            pub fn insert_json<T: serde::Serialize + ?Sized>(
+                &self,
+                key: &str,
+                val: &T,
+            ) -> std::io::Result<()> {
+                crate::fs::Storage::insert_json(self, key, val)
+            }
+
+            /// Returns the value corresponding to the key, migrated to the current
+            /// version of the schema. See [`Schema`] for more.
+            ///
+            /// [`Schema`]: crate::fs::Schema
+            ///
+            /// # Errors
+            ///
+            /// An error of kind `io::ErrorKind::InvalidData` is returned if the value
+            /// could not be migrated or deserialized.
+            pub fn get_versioned<T: serde::de::DeserializeOwned>(
+                &self,
+                key: &str,
+                schema: &crate::fs::Schema,
+            ) -> std::io::Result<Option<T>> {
+                crate::fs::Storage::get_versioned(self, key, schema)
+            }
+
+            /// Inserts a value tagged with the current version of the schema.
+            ///
+            /// # Errors
+            ///
+            /// An error of kind `io::ErrorKind::InvalidData` is returned if the value
+            /// could not be serialized, and an error may be returned if the storage
+            /// is full.
+            pub fn insert_versioned<T: serde::Serialize + ?Sized>(
+                &self,
+                key: &str,
+                val: &T,
+                schema: &crate::fs::Schema,
+            ) -> std::io::Result<()> {
+                crate::fs::Storage::insert_versioned(self, key, val, schema)
+            }
+
+            /// Returns the name of the key at an index.
+            pub fn key(&self, index: usize) -> Option<String> {
+                self.storage.key(index)
+            }
+
+            #[doc = concat!("Returns all keys in the ", $kind, " Storage.")]
+            pub fn keys(&self) -> Vec<String> {
+                self.storage.keys()
+            }
+
+            #[doc = concat!("Returns an iterator over all key-value pairs in the ", $kind, " Storage.")]
+            pub fn iter(&self) -> crate::fs::Iter<'_, Self> {
+                crate::fs::Storage::iter(self)
+            }
+
+            #[doc = concat!("Removes all key-value pairs from the ", $kind, " Storage, and returns them")]
+            /// as an iterator.
+            pub fn drain(&self) -> crate::fs::Drain {
+                crate::fs::Storage::drain(self)
+            }
+
+            $(#[$changes_attr])*
+            ///
+            /// Changes made through this instance are not included.
+            ///
+            /// # Examples
+            ///
+            /// ```no_run
+            /// use localghost::prelude::*;
+            /// use localghost::{fs, log};
+            /// use async_std::prelude::*;
+            /// use std::io;
+            ///
+            /// #[localghost::main]
+            /// async fn main() -> io::Result<()> {
+            #[doc = concat!("    let storage = fs::", stringify!($name), "::open()?;")]
+            ///     let mut changes = storage.changes();
+            ///     while let Some(change) = changes.next().await {
+            ///         match change.key() {
+            ///             Some(key) => log::info!("{} changed to {:?}", key, change.new_value()),
+            ///             None => log::info!("storage was cleared"),
+            ///         }
+            ///     }
+            ///     Ok(())
+            /// }
+            /// ```
+            pub fn changes(&self) -> crate::fs::StorageChanges {
+                self.storage.changes(crate::fs::StorageArea::$area)
+            }
+
+            #[doc = concat!("Returns a view of the ", $kind, " Storage which transparently prefixes all")]
+            /// keys.
+            ///
+            /// # Examples
+            ///
+            /// ```no_run
+            /// use localghost::prelude::*;
+            #[doc = concat!("use localghost::fs::{", stringify!($name), ", Storage};")]
+            /// use std::io;
+            ///
+            /// #[localghost::main]
+            /// async fn main() -> io::Result<()> {
+            #[doc = concat!("    let storage = ", stringify!($name), "::open()?;")]
+            ///     let app = storage.namespace("app:");
+            ///     app.insert("theme", "dark")?;
+            ///     assert_eq!(storage.get("app:theme").as_deref(), Some("dark"));
+            ///
+            ///     // Only removes keys starting with "app:".
+            ///     app.clear();
+            ///     Ok(())
+            /// }
+            /// ```
+            pub fn namespace(&self, prefix: &str) -> crate::fs::Namespace<'_, Self> {
+                crate::fs::Namespace::new(self, prefix)
+            }
+        }
+
+        impl crate::fs::Storage for $name {
+            fn get(&self, key: &str) -> Option<String> {
+                self.get(key)
+            }
+
+            fn insert(&self, key: &str, val: &str) -> std::io::Result<()> {
+                self.insert(key, val)
+            }
+
+            fn remove(&self, key: &str) -> Option<String> {
+                self.remove(key)
+            }
+
+            fn clear(&self) {
+                self.clear()
+            }
+
+            fn len(&self) -> usize {
+                self.len()
+            }
+
+            fn key(&self, index: usize) -> Option<String> {
+                self.key(index)
+            }
+
+            fn keys(&self) -> Vec<String> {
+                self.keys()
+            }
+        }
+    };
+}
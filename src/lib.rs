@@ -65,6 +65,7 @@ pub mod keyboard;
 pub mod log;
 pub mod net;
 pub mod prelude;
+pub mod reactive;
 pub mod task;
 
 mod history;
@@ -73,6 +74,8 @@ mod utils;
 
 pub use history::History;
 #[doc(inline)]
+pub use localghost_macros::html;
+#[doc(inline)]
 pub use localghost_macros::main;
 pub use location::Location;
 
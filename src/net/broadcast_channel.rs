@@ -0,0 +1,126 @@
+use crate::events::EventListener;
+use crate::prelude::*;
+use crate::utils::ResultExt;
+
+use async_channel::{self as channel, Receiver};
+use futures_core::Stream;
+use wasm_bindgen::JsCast;
+
+use std::{io, pin::Pin, task::Poll};
+
+/// A named channel for sending text messages to other browsing contexts, such
+/// as tabs and workers, of the same origin.
+///
+/// Messages are not delivered to the instance which sent them. Messages which
+/// are not strings are ignored.
+///
+/// [Read more](https://developer.mozilla.org/en-US/docs/Web/API/BroadcastChannel)
+///
+/// # Examples
+///
+/// ```no_run
+/// use localghost::prelude::*;
+/// use localghost::{log, net};
+/// use async_std::prelude::*;
+///
+/// use std::io;
+///
+/// #[localghost::main]
+/// async fn main() -> io::Result<()> {
+///     let mut channel = net::BroadcastChannel::open("chat")?;
+///     channel.send("hello from another tab")?;
+///
+///     while let Some(msg) = channel.next().await {
+///         log::info!("message: {}", msg);
+///     }
+///     Ok(())
+/// }
+/// ```
+#[pin_project::pin_project(PinnedDrop)]
+#[derive(Debug)]
+pub struct BroadcastChannel {
+    inner: web_sys::BroadcastChannel,
+    name: String,
+    _listener: EventListener,
+    #[pin]
+    receiver: Receiver<String>,
+}
+
+impl BroadcastChannel {
+    /// Join the channel with the given name.
+    ///
+    /// # Errors
+    ///
+    /// An error will be returned if the channel could not be opened.
+    pub fn open(name: &str) -> io::Result<Self> {
+        let inner = web_sys::BroadcastChannel::new(name).err_kind(io::ErrorKind::Other)?;
+        let (sender, receiver) = channel::unbounded();
+        let _listener = EventListener::listen(&inner, "message", move |ev| {
+            let ev = ev
+                .into_raw()
+                .dyn_into::<web_sys::MessageEvent>()
+                .unwrap_throw();
+            if let Some(msg) = ev.data().as_string() {
+                let _ = sender.try_send(msg);
+            }
+        });
+
+        Ok(Self {
+            inner,
+            name: name.to_owned(),
+            _listener,
+            receiver,
+        })
+    }
+
+    /// Get the name of the channel.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Send a message to all other instances listening on the channel.
+    ///
+    /// # Errors
+    ///
+    /// An error will be returned if the message could not be sent.
+    pub fn send(&self, msg: &str) -> io::Result<()> {
+        self.inner
+            .post_message(&JsValue::from_str(msg))
+            .err_kind(io::ErrorKind::Other)
+    }
+
+    /// Receive a message from the channel.
+    pub async fn recv(&self) -> io::Result<String> {
+        self.receiver.recv().await.map_err(|_| {
+            io::Error::new(
+                io::ErrorKind::NotConnected,
+                format!("BroadcastChannel({}): channel closed", self.name),
+            )
+        })
+    }
+}
+
+#[pin_project::pinned_drop]
+impl PinnedDrop for BroadcastChannel {
+    fn drop(self: Pin<&mut Self>) {
+        self.inner.close();
+    }
+}
+
+impl AsRef<web_sys::EventTarget> for BroadcastChannel {
+    fn as_ref(&self) -> &web_sys::EventTarget {
+        self.inner.as_ref()
+    }
+}
+
+impl Stream for BroadcastChannel {
+    type Item = String;
+
+    fn poll_next(
+        self: Pin<&mut Self>,
+        cx: &mut std::task::Context<'_>,
+    ) -> Poll<Option<Self::Item>> {
+        let this = self.project();
+        this.receiver.poll_next(cx)
+    }
+}
@@ -0,0 +1,290 @@
+use std::io;
+
+use js_sys::Array;
+use wasm_bindgen::JsCast;
+use wasm_bindgen_futures::JsFuture;
+
+use crate::net::{Request, Response};
+use crate::prelude::*;
+use crate::utils::{self, ResultExt};
+
+/// The header which records when a response was fetched.
+///
+/// The `Date` header isn't exposed on cross-origin responses, so responses
+/// are stamped with the time they were stored in the cache instead.
+const FETCHED_AT: &str = "x-localghost-fetched-at";
+
+/// A named store of HTTP responses, backed by the Cache Storage API.
+///
+/// [Read more](https://developer.mozilla.org/en-US/docs/Web/API/Cache)
+///
+/// # Examples
+///
+/// ```no_run
+/// use localghost::prelude::*;
+/// use localghost::{log, net};
+/// use std::io;
+///
+/// #[localghost::main]
+/// async fn main() -> io::Result<()> {
+///     let url = "https://example.com";
+///     let cache = net::Cache::open("v1").await?;
+///     let res = net::Request::get(url).send().await?;
+///     cache.put(url, &res).await?;
+///
+///     let res = cache.match_url(url).await?;
+///     log::info!("cached: {:?}", res.is_some());
+///     Ok(())
+/// }
+/// ```
+#[derive(Debug)]
+pub struct Cache {
+    name: String,
+    inner: web_sys::Cache,
+}
+
+impl Cache {
+    /// Open the cache with the given name, creating it if it doesn't exist yet.
+    ///
+    /// # Errors
+    ///
+    /// An error will be returned if the Cache Storage API is not available,
+    /// which is the case for documents not served from a secure context.
+    pub async fn open(name: &str) -> io::Result<Self> {
+        let caches = utils::window()
+            .caches()
+            .err_kind(io::ErrorKind::PermissionDenied)?;
+        let fut = JsFuture::from(caches.open(name));
+        let inner = fut.await.err_kind(io::ErrorKind::PermissionDenied)?;
+        debug_assert!(inner.is_instance_of::<web_sys::Cache>());
+
+        Ok(Self {
+            name: name.to_owned(),
+            inner: inner.dyn_into().unwrap_throw(),
+        })
+    }
+
+    /// Get the name of the cache.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Get the response stored for a url.
+    ///
+    /// [Read more](https://developer.mozilla.org/en-US/docs/Web/API/Cache/match)
+    pub async fn match_url(&self, url: &str) -> io::Result<Option<Response>> {
+        let fut = JsFuture::from(self.inner.match_with_str(url));
+        let res = fut.await.err_kind(io::ErrorKind::Other)?;
+        if res.is_undefined() {
+            return Ok(None);
+        }
+
+        debug_assert!(res.is_instance_of::<web_sys::Response>());
+        Ok(Some(Response::new(res.dyn_into().unwrap_throw())))
+    }
+
+    /// Store a response for a url, replacing any previously stored response.
+    ///
+    /// A copy of the response is stored, so `res` can still be read afterwards.
+    ///
+    /// # Errors
+    ///
+    /// An error will be returned if the body of the response has already been
+    /// read, or if the response could not be stored.
+    pub async fn put(&self, url: &str, res: &Response) -> io::Result<()> {
+        let res = res.try_clone()?;
+        let fut = JsFuture::from(self.inner.put_with_str(url, res.as_raw()));
+        fut.await.err_kind(io::ErrorKind::Other)?;
+        Ok(())
+    }
+
+    /// Remove the response stored for a url.
+    ///
+    /// Returns `true` if a response was removed.
+    pub async fn delete(&self, url: &str) -> io::Result<bool> {
+        let fut = JsFuture::from(self.inner.delete_with_str(url));
+        let deleted = fut.await.err_kind(io::ErrorKind::Other)?;
+        Ok(deleted.is_truthy())
+    }
+
+    /// Get the urls of all requests stored in the cache.
+    pub async fn keys(&self) -> io::Result<Vec<String>> {
+        let fut = JsFuture::from(self.inner.keys());
+        let keys = fut.await.err_kind(io::ErrorKind::Other)?;
+        let keys: Array = keys.dyn_into().unwrap_throw();
+        let urls = keys
+            .iter()
+            .map(|req| req.unchecked_into::<web_sys::Request>().url())
+            .collect();
+        Ok(urls)
+    }
+}
+
+/// How a [`Request`] should be served from its cache.
+///
+/// See [`Request::set_cache_strategy`] for more.
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash)]
+pub enum CacheStrategy {
+    /// Serve fresh responses from the cache, and only go to the network when
+    /// no fresh response was cached. A stale response is used if the network
+    /// is unreachable.
+    CacheFirst,
+    /// Go to the network first, and fall back to a cached response if the
+    /// network is unreachable.
+    NetworkFirst,
+    /// Serve cached responses immediately, and update stale responses in the
+    /// background.
+    StaleWhileRevalidate,
+}
+
+/// Send a request through the named cache.
+pub(crate) async fn send(
+    req: Request,
+    name: &str,
+    strategy: CacheStrategy,
+) -> io::Result<Response> {
+    let directives = req
+        .header("cache-control")
+        .map(|h| CacheControl::parse(&h))
+        .unwrap_or_default();
+    if directives.no_store {
+        return req.fetch().await;
+    }
+
+    let cache = Cache::open(name).await?;
+    let cached = match directives.no_cache {
+        true => None,
+        false => cache.match_url(req.url()).await?,
+    };
+
+    match strategy {
+        CacheStrategy::CacheFirst => match cached {
+            Some(res) if is_fresh(&res) => Ok(res),
+            cached => match revalidate(&cache, &req).await {
+                Ok(res) => Ok(res),
+                Err(err) => cached.ok_or(err),
+            },
+        },
+        CacheStrategy::NetworkFirst => match revalidate(&cache, &req).await {
+            Ok(res) => Ok(res),
+            Err(err) => cached.ok_or(err),
+        },
+        CacheStrategy::StaleWhileRevalidate => match cached {
+            Some(res) => {
+                if !is_fresh(&res) {
+                    crate::task::spawn_local(async move {
+                        if let Err(err) = revalidate(&cache, &req).await {
+                            crate::log::debug!(
+                                "Cache({}): revalidation failed: {}",
+                                cache.name,
+                                err
+                            );
+                        }
+                    });
+                }
+                Ok(res)
+            }
+            None => revalidate(&cache, &req).await,
+        },
+    }
+}
+
+/// Fetch a response from the network, and store it if it may be cached.
+async fn revalidate(cache: &Cache, req: &Request) -> io::Result<Response> {
+    let res = req.fetch().await?;
+    let directives = CacheControl::from_response(&res);
+    if res.as_raw().ok() && !directives.no_store {
+        let stored = match stamp(&res) {
+            Ok(stamped) => cache.put(req.url(), &stamped).await,
+            Err(err) => Err(err),
+        };
+        if let Err(err) = stored {
+            crate::log::warn!("Cache({}): could not store response: {}", cache.name, err);
+        }
+    }
+    Ok(res)
+}
+
+/// Copy a response, adding the time it was fetched.
+fn stamp(res: &Response) -> io::Result<Response> {
+    let copy = res.try_clone()?;
+    let raw = copy.as_raw();
+    let headers = web_sys::Headers::new_with_headers(&raw.headers()).unwrap_throw();
+    headers
+        .set(FETCHED_AT, &js_sys::Date::now().to_string())
+        .unwrap_throw();
+
+    let init = web_sys::ResponseInit::new();
+    init.set_status(raw.status());
+    init.set_status_text(&raw.status_text());
+    init.set_headers_headers(&headers);
+    let stamped =
+        web_sys::Response::new_with_opt_readable_stream_and_init(raw.body().as_ref(), &init)
+            .err_kind(io::ErrorKind::Other)?;
+    Ok(Response::new(stamped))
+}
+
+/// Check whether a cached response can be served without revalidating it.
+fn is_fresh(res: &Response) -> bool {
+    let directives = CacheControl::from_response(res);
+    let max_age = match directives.max_age {
+        Some(max_age) if !directives.no_cache => max_age as f64,
+        _ => return false,
+    };
+
+    // Fall back to the time the response was stored if the `Date` header
+    // can't be read, as is the case for cross-origin responses.
+    let date = res
+        .header("date")
+        .map(|date| js_sys::Date::parse(&date))
+        .filter(|date| !date.is_nan())
+        .or_else(|| res.header(FETCHED_AT)?.parse::<f64>().ok());
+    let date = match date {
+        Some(date) => date,
+        None => return false,
+    };
+
+    // The age of a response is the largest of the age reported by upstream
+    // caches, and the time elapsed since the origin generated it.
+    let elapsed = ((js_sys::Date::now() - date) / 1000.0).max(0.0);
+    let age = res
+        .header("age")
+        .and_then(|age| age.parse::<f64>().ok())
+        .unwrap_or(0.0);
+    elapsed.max(age) < max_age
+}
+
+/// The `Cache-Control` directives relevant to a client-side cache.
+#[derive(Debug, Default)]
+struct CacheControl {
+    no_store: bool,
+    no_cache: bool,
+    max_age: Option<u64>,
+}
+
+impl CacheControl {
+    /// Parse the directives from a `Cache-Control` header value.
+    fn parse(header: &str) -> Self {
+        let mut this = Self::default();
+        for directive in header.split(',') {
+            let (name, value) = match directive.trim().split_once('=') {
+                Some((name, value)) => (name, Some(value.trim_matches('"'))),
+                None => (directive.trim(), None),
+            };
+            match name.to_ascii_lowercase().as_str() {
+                "no-store" => this.no_store = true,
+                "no-cache" => this.no_cache = true,
+                "max-age" => this.max_age = value.and_then(|v| v.parse().ok()),
+                _ => {}
+            }
+        }
+        this
+    }
+
+    /// Get the directives set on a response.
+    fn from_response(res: &Response) -> Self {
+        res.header("cache-control")
+            .map(|h| Self::parse(&h))
+            .unwrap_or_default()
+    }
+}
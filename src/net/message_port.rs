@@ -0,0 +1,119 @@
+use crate::events::EventListener;
+use crate::prelude::*;
+use crate::utils::ResultExt;
+
+use async_channel::{self as channel, Receiver};
+use futures_core::Stream;
+use wasm_bindgen::JsCast;
+
+use std::{io, pin::Pin, task::Poll};
+
+/// One end of a two-way channel for sending text messages, such as between a
+/// document and a worker.
+///
+/// Messages which are not strings are ignored.
+///
+/// [Read more](https://developer.mozilla.org/en-US/docs/Web/API/MessagePort)
+///
+/// # Examples
+///
+/// ```no_run
+/// use localghost::prelude::*;
+/// use localghost::{log, net};
+///
+/// use std::io;
+///
+/// #[localghost::main]
+/// async fn main() -> io::Result<()> {
+///     let (left, right) = net::MessagePort::pair();
+///     left.send("ping")?;
+///     log::info!("received: {}", right.recv().await?);
+///     Ok(())
+/// }
+/// ```
+#[pin_project::pin_project(PinnedDrop)]
+#[derive(Debug)]
+pub struct MessagePort {
+    inner: web_sys::MessagePort,
+    _listener: EventListener,
+    #[pin]
+    receiver: Receiver<String>,
+}
+
+impl MessagePort {
+    /// Create a new channel, and return both of its ports.
+    pub fn pair() -> (Self, Self) {
+        let channel = web_sys::MessageChannel::new().unwrap_throw();
+        (
+            Self::from_raw(channel.port1()),
+            Self::from_raw(channel.port2()),
+        )
+    }
+
+    /// Create a new instance from a raw `MessagePort`, and start receiving
+    /// messages from it.
+    pub fn from_raw(inner: web_sys::MessagePort) -> Self {
+        let (sender, receiver) = channel::unbounded();
+        let _listener = EventListener::listen(&inner, "message", move |ev| {
+            let ev = ev
+                .into_raw()
+                .dyn_into::<web_sys::MessageEvent>()
+                .unwrap_throw();
+            if let Some(msg) = ev.data().as_string() {
+                let _ = sender.try_send(msg);
+            }
+        });
+
+        // Ports don't dispatch messages to listeners until they're started.
+        inner.start();
+        Self {
+            inner,
+            _listener,
+            receiver,
+        }
+    }
+
+    /// Send a message to the other end of the channel.
+    ///
+    /// # Errors
+    ///
+    /// An error will be returned if the message could not be sent.
+    pub fn send(&self, msg: &str) -> io::Result<()> {
+        self.inner
+            .post_message(&JsValue::from_str(msg))
+            .err_kind(io::ErrorKind::Other)
+    }
+
+    /// Receive a message from the other end of the channel.
+    pub async fn recv(&self) -> io::Result<String> {
+        self.receiver
+            .recv()
+            .await
+            .map_err(|_| io::Error::new(io::ErrorKind::NotConnected, "MessagePort: port closed"))
+    }
+}
+
+#[pin_project::pinned_drop]
+impl PinnedDrop for MessagePort {
+    fn drop(self: Pin<&mut Self>) {
+        self.inner.close();
+    }
+}
+
+impl AsRef<web_sys::EventTarget> for MessagePort {
+    fn as_ref(&self) -> &web_sys::EventTarget {
+        self.inner.as_ref()
+    }
+}
+
+impl Stream for MessagePort {
+    type Item = String;
+
+    fn poll_next(
+        self: Pin<&mut Self>,
+        cx: &mut std::task::Context<'_>,
+    ) -> Poll<Option<Self::Item>> {
+        let this = self.project();
+        this.receiver.poll_next(cx)
+    }
+}
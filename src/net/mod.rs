@@ -18,18 +18,34 @@
 
 mod beacon;
 mod body;
+mod broadcast_channel;
+mod cache;
 mod event_source;
 mod headers;
 mod message_event;
+mod message_port;
+mod outbox;
+mod query_cache;
+mod reconnecting_websocket;
 mod request;
 mod response;
+mod rpc;
+mod websocket;
 
 use headers::Headers;
 
 pub use beacon::Beacon;
 pub use body::Body;
+pub use broadcast_channel::BroadcastChannel;
+pub use cache::{Cache, CacheStrategy};
 pub use event_source::{EventSource, ReadyState};
 pub use headers::HeadersIter;
 pub use message_event::MessageEvent;
+pub use message_port::MessagePort;
+pub use outbox::{Outbox, OutboxEvent, OutboxStatus, OutboxStream};
+pub use query_cache::{QueryCache, QueryStream};
+pub use reconnecting_websocket::{ConnectionState, ReconnectingWebSocket, StateStream};
 pub use request::Request;
 pub use response::Response;
+pub use rpc::{Notification, Notifications, Rpc, RpcError, Transport};
+pub use websocket::{Message, WebSocket, WebSocketState};
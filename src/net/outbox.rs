@@ -0,0 +1,439 @@
+use std::cell::RefCell;
+use std::collections::VecDeque;
+use std::io;
+use std::pin::Pin;
+use std::rc::{Rc, Weak};
+use std::task::{Context, Poll};
+use std::time::Duration;
+
+use async_channel::{self as channel, Receiver, Sender};
+use futures_core::Stream;
+use futures_timer::Delay;
+use pin_project::pin_project;
+use serde::{Deserialize, Serialize};
+
+use crate::events::EventListener;
+use crate::fs::LocalStorage;
+use crate::net::{Request, Response};
+use crate::prelude::*;
+use crate::task::Lock;
+use crate::utils;
+
+/// The maximum delay between two attempts to send a request.
+const MAX_BACKOFF: Duration = Duration::from_secs(60);
+
+/// A persistent queue of requests which are sent once the browser is online.
+///
+/// Requests pushed onto the outbox are written to `LocalStorage`, so they
+/// survive page reloads. Whenever the browser is online the outbox sends its
+/// requests one at a time, in the order they were pushed: right away when the
+/// outbox is opened, when the browser comes back online, and periodically
+/// while it's offline. Requests which fail because of a network error, or
+/// which receive a `5xx`, `408` or `429` response, are retried with
+/// exponential backoff. Requests which are invalid, such as a `GET` request
+/// with a body, fail right away.
+///
+/// Outboxes with the same name share their queue across tabs. Only one of
+/// them sends requests at a time, and it reports their status to its own
+/// [`events`] subscribers only.
+///
+/// Sending stops when the outbox is dropped.
+///
+/// [`events`]: Outbox::events
+///
+/// # Examples
+///
+/// ```no_run
+/// use localghost::prelude::*;
+/// use localghost::{log, net};
+/// use async_std::prelude::*;
+/// use std::io;
+///
+/// #[localghost::main]
+/// async fn main() -> io::Result<()> {
+///     let outbox = net::Outbox::open("mutations")?;
+///     let mut events = outbox.events();
+///
+///     let mut req = net::Request::post("https://httpbin.org/post");
+///     req.set_body("hello world");
+///     outbox.push(req)?;
+///
+///     while let Some(ev) = events.next().await {
+///         log::info!("request {}: {:?}", ev.id(), ev.status());
+///     }
+///     Ok(())
+/// }
+/// ```
+#[derive(Debug)]
+pub struct Outbox {
+    inner: Rc<RefCell<Inner>>,
+    _listener: EventListener,
+}
+
+#[derive(Debug)]
+struct Inner {
+    key: String,
+    storage: LocalStorage,
+    max_attempts: u32,
+    flushing: bool,
+    subscribers: Vec<Sender<OutboxEvent>>,
+}
+
+/// The queue as it's written to storage.
+///
+/// Other tabs may change it at any time, so it's read right before every
+/// change, and written back right after.
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct StoredQueue {
+    next_id: u64,
+    requests: VecDeque<StoredRequest>,
+}
+
+/// A `Request` in a form which can be written to storage.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct StoredRequest {
+    id: u64,
+    method: String,
+    url: String,
+    headers: Vec<(String, String)>,
+    body: Option<Vec<u8>>,
+    attempts: u32,
+}
+
+impl StoredRequest {
+    fn new(id: u64, req: &Request) -> Self {
+        Self {
+            id,
+            method: req.method().to_owned(),
+            url: req.url().to_owned(),
+            headers: req.headers().collect(),
+            body: req.body().map(|body| body.to_vec()),
+            attempts: 0,
+        }
+    }
+
+    fn to_request(&self) -> Request {
+        let mut req = Request::new(&self.method, &self.url);
+        for (name, value) in &self.headers {
+            req.insert_header(name, value);
+        }
+        if let Some(body) = &self.body {
+            req.set_body(body.clone());
+        }
+        req
+    }
+}
+
+impl Outbox {
+    /// Open the outbox with the given name, and start sending any requests
+    /// which were queued in an earlier session.
+    ///
+    /// # Errors
+    ///
+    /// An error will be returned if `LocalStorage` cannot be accessed, or if
+    /// the stored queue could not be read.
+    pub fn open(name: &str) -> io::Result<Self> {
+        let inner = Inner {
+            key: format!("localghost:outbox:{}", name),
+            storage: LocalStorage::open()?,
+            max_attempts: 5,
+            flushing: false,
+            subscribers: vec![],
+        };
+        inner.load()?;
+
+        let inner = Rc::new(RefCell::new(inner));
+        let inner2 = inner.clone();
+        let _listener = utils::window().on_with("online", move |_| flush(&inner2));
+        flush(&inner);
+
+        Ok(Self { inner, _listener })
+    }
+
+    /// Push a request onto the outbox.
+    ///
+    /// Returns the id which identifies the request in [`OutboxEvent`]s.
+    ///
+    /// # Errors
+    ///
+    /// An error will be returned if the request could not be persisted.
+    pub fn push(&self, req: Request) -> io::Result<u64> {
+        let id = {
+            let mut inner = self.inner.borrow_mut();
+            let id = inner.update(|queue| {
+                let id = queue.next_id;
+                queue.next_id += 1;
+                queue.requests.push_back(StoredRequest::new(id, &req));
+                id
+            })?;
+            if !utils::window().navigator().on_line() {
+                inner.emit(id, OutboxStatus::Queued);
+            }
+            id
+        };
+
+        flush(&self.inner);
+        Ok(id)
+    }
+
+    /// Returns the number of requests waiting to be sent.
+    pub fn len(&self) -> usize {
+        let inner = self.inner.borrow();
+        inner.load().map_or(0, |queue| queue.requests.len())
+    }
+
+    /// Returns `true` if no requests are waiting to be sent.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Set how many times a request is attempted before it's dropped.
+    ///
+    /// Defaults to `5`.
+    pub fn set_max_attempts(&self, max_attempts: u32) {
+        self.inner.borrow_mut().max_attempts = max_attempts.max(1);
+    }
+
+    /// Create a stream of status updates for the requests in the outbox.
+    pub fn events(&self) -> OutboxStream {
+        let (sender, receiver) = channel::unbounded();
+        self.inner.borrow_mut().subscribers.push(sender);
+        OutboxStream { receiver }
+    }
+}
+
+impl Inner {
+    /// Read the queue from storage.
+    fn load(&self) -> io::Result<StoredQueue> {
+        match self.storage.get(&self.key) {
+            Some(json) => serde_json::from_str(&json)
+                .map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err)),
+            None => Ok(StoredQueue::default()),
+        }
+    }
+
+    /// Read the queue from storage, change it, and write it back.
+    fn update<F, T>(&self, f: F) -> io::Result<T>
+    where
+        F: FnOnce(&mut StoredQueue) -> T,
+    {
+        let mut queue = self.load()?;
+        let res = f(&mut queue);
+        let json = serde_json::to_string(&queue)
+            .map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err))?;
+        self.storage.insert(&self.key, &json)?;
+        Ok(res)
+    }
+
+    /// Get the request at the front of the queue.
+    fn front(&self) -> Option<StoredRequest> {
+        match self.load() {
+            Ok(mut queue) => queue.requests.pop_front(),
+            Err(err) => {
+                crate::log::warn!("Outbox({}): could not read queue: {}", self.key, err);
+                None
+            }
+        }
+    }
+
+    /// Notify all subscribers of a status change.
+    fn emit(&mut self, id: u64, status: OutboxStatus) {
+        let ev = OutboxEvent { id, status };
+        self.subscribers
+            .retain(|sender| sender.try_send(ev.clone()).is_ok());
+    }
+
+    /// Remove a request from the queue.
+    fn remove(&self, id: u64) {
+        let res = self.update(|queue| queue.requests.retain(|req| req.id != id));
+        if let Err(err) = res {
+            crate::log::warn!("Outbox({}): could not persist queue: {}", self.key, err);
+        }
+    }
+}
+
+/// What to do after attempting to send a request.
+enum Next {
+    Continue,
+    Retry(Duration),
+    Stop,
+}
+
+/// Send all queued requests, unless the outbox is already doing so.
+///
+/// Requests are only sent while holding a lock named after the outbox, so
+/// that outboxes in other tabs don't send them too. Only a weak reference is
+/// held while waiting, so that sending stops once the outbox is dropped.
+fn flush(inner: &Rc<RefCell<Inner>>) {
+    {
+        let mut inner = inner.borrow_mut();
+        if inner.flushing {
+            return;
+        }
+        inner.flushing = true;
+    }
+
+    let weak = Rc::downgrade(inner);
+    let name = inner.borrow().key.clone();
+    crate::task::spawn_local(async move {
+        // Browsers without the Web Locks API send requests without
+        // coordinating with other tabs.
+        let _lock = Lock::request(&name).await.ok();
+
+        let retry_later = loop {
+            let inner = match weak.upgrade() {
+                Some(inner) => inner,
+                None => return,
+            };
+            if !utils::window().navigator().on_line() {
+                break inner.borrow().front().is_some();
+            }
+
+            let req = {
+                let mut this = inner.borrow_mut();
+                let req = match this.front() {
+                    Some(req) => req,
+                    None => break false,
+                };
+                this.emit(req.id, OutboxStatus::Sending);
+                req
+            };
+
+            let res = req.to_request().fetch().await;
+            let next = settle(&mut inner.borrow_mut(), &req, res);
+            drop(inner);
+            match next {
+                Next::Continue => {}
+                Next::Retry(delay) => Delay::new(delay).await,
+                Next::Stop => break true,
+            }
+        };
+
+        if let Some(inner) = weak.upgrade() {
+            inner.borrow_mut().flushing = false;
+        }
+        // `navigator.onLine` may be wrong about being offline, so don't rely
+        // on the `online` event alone to try again.
+        if retry_later {
+            flush_later(weak, MAX_BACKOFF);
+        }
+    });
+}
+
+/// Flush the outbox after a delay, unless it's dropped by then.
+fn flush_later(inner: Weak<RefCell<Inner>>, delay: Duration) {
+    crate::task::spawn_local(async move {
+        Delay::new(delay).await;
+        if let Some(inner) = inner.upgrade() {
+            flush(&inner);
+        }
+    });
+}
+
+/// Record the outcome of sending a request.
+fn settle(inner: &mut Inner, req: &StoredRequest, res: io::Result<Response>) -> Next {
+    let id = req.id;
+    match res.map(|res| res.status()) {
+        Ok(status) if !matches!(status, 408 | 429 | 500..=599) => {
+            inner.remove(id);
+            inner.emit(id, OutboxStatus::Sent(status));
+            return Next::Continue;
+        }
+        // Invalid requests fail the same way every time.
+        Err(err) if err.kind() == io::ErrorKind::InvalidInput => {
+            inner.remove(id);
+            inner.emit(id, OutboxStatus::Failed);
+            return Next::Continue;
+        }
+        // Requests which fail because we went offline don't count as an
+        // attempt; they're retried once we're back online.
+        Err(_) if !utils::window().navigator().on_line() => {
+            inner.emit(id, OutboxStatus::Queued);
+            return Next::Stop;
+        }
+        _ => {}
+    }
+
+    let attempt = req.attempts + 1;
+    if attempt >= inner.max_attempts {
+        inner.remove(id);
+        inner.emit(id, OutboxStatus::Failed);
+        return Next::Continue;
+    }
+
+    let res = inner.update(|queue| {
+        if let Some(req) = queue.requests.iter_mut().find(|req| req.id == id) {
+            req.attempts = attempt;
+        }
+    });
+    if let Err(err) = res {
+        crate::log::warn!("Outbox({}): could not persist queue: {}", inner.key, err);
+    }
+    inner.emit(id, OutboxStatus::Retrying { attempt });
+    let delay = Duration::from_secs(1 << attempt.min(6)).min(MAX_BACKOFF);
+    Next::Retry(delay)
+}
+
+/// A status update for a request in an [`Outbox`].
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct OutboxEvent {
+    id: u64,
+    status: OutboxStatus,
+}
+
+impl OutboxEvent {
+    /// Get the id of the request, as returned by [`Outbox::push`].
+    pub fn id(&self) -> u64 {
+        self.id
+    }
+
+    /// Get the status of the request.
+    pub fn status(&self) -> &OutboxStatus {
+        &self.status
+    }
+}
+
+/// The status of a request in an [`Outbox`].
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum OutboxStatus {
+    /// The request is waiting for the browser to come online.
+    ///
+    /// Only reported while the browser is offline.
+    Queued,
+    /// The request is being sent.
+    Sending,
+    /// The request failed, and will be sent again after a delay.
+    Retrying {
+        /// The number of attempts made so far.
+        attempt: u32,
+    },
+    /// A response was received with the given status code. The request has
+    /// been removed from the outbox.
+    Sent(u16),
+    /// The request failed too many times, and has been removed from the outbox.
+    Failed,
+}
+
+/// A stream of [`OutboxEvent`]s.
+///
+/// This `struct` is created by the [`events`] method on [`Outbox`]. See its
+/// documentation for more.
+///
+/// [`events`]: struct.Outbox.html#method.events
+/// [`Outbox`]: struct.Outbox.html
+#[pin_project]
+#[derive(Debug)]
+#[must_use = "Streams do nothing unless polled"]
+pub struct OutboxStream {
+    #[pin]
+    receiver: Receiver<OutboxEvent>,
+}
+
+impl Stream for OutboxStream {
+    type Item = OutboxEvent;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = self.project();
+        this.receiver.poll_next(cx)
+    }
+}
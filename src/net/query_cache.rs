@@ -0,0 +1,459 @@
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::io;
+use std::pin::Pin;
+use std::rc::Rc;
+use std::task::{Context, Poll};
+use std::time::Duration;
+
+use async_channel::{self as channel, Receiver, Sender};
+use futures_channel::oneshot;
+use futures_core::Stream;
+use js_sys::Uint8Array;
+use pin_project::pin_project;
+
+use crate::net::{Request, Response};
+use crate::prelude::*;
+
+/// The number of entries a cache holds unless configured otherwise.
+const DEFAULT_MAX_ENTRIES: usize = 100;
+
+/// An in-memory cache of HTTP responses which deduplicates requests.
+///
+/// Responses are keyed by their method and url. Identical `GET` requests which
+/// are in flight at the same time share a single call to [`Request::send`],
+/// and cached responses are served immediately while they're revalidated in
+/// the background ("stale-while-revalidate"). Cloning a `QueryCache` creates a
+/// new handle to the same cache.
+///
+/// The cache holds at most 100 entries by default, see
+/// [`set_max_entries`](#method.set_max_entries). Once it's full, the least
+/// recently used entry which nobody is waiting on or subscribed to is evicted.
+///
+/// # Examples
+///
+/// ```no_run
+/// use localghost::prelude::*;
+/// use localghost::{log, net};
+/// use async_std::prelude::*;
+/// use std::io;
+///
+/// #[localghost::main]
+/// async fn main() -> io::Result<()> {
+///     let cache = net::QueryCache::new();
+///     let url = "https://httpbin.org/uuid";
+///
+///     // Both requests are coalesced into a single `fetch` call.
+///     let (a, b) = (net::Request::get(url), net::Request::get(url));
+///     let (a, b) = cache.fetch(a).join(cache.fetch(b)).await;
+///     let (a, b) = (a?, b?);
+///     log::info!("a: {:?}", a.body_string().await?);
+///     log::info!("b: {:?}", b.body_string().await?);
+///
+///     // Mark the response as stale so the next fetch refreshes it.
+///     cache.invalidate(url);
+///     Ok(())
+/// }
+/// ```
+#[derive(Debug, Clone)]
+pub struct QueryCache {
+    inner: Rc<RefCell<Inner>>,
+}
+
+#[derive(Debug)]
+struct Inner {
+    stale_time: Duration,
+    max_entries: usize,
+    entries: HashMap<QueryKey, Entry>,
+}
+
+impl Inner {
+    /// Get the entry for a key, creating it if needed.
+    fn entry(&mut self, key: &QueryKey, req: &Request) -> &mut Entry {
+        if !self.entries.contains_key(key) {
+            self.evict(self.max_entries.saturating_sub(1));
+        }
+        let entry = self
+            .entries
+            .entry(key.clone())
+            .or_insert_with(|| Entry::new(req.clone()));
+        entry.used_at = js_sys::Date::now();
+        entry
+    }
+
+    /// Evict the least recently used entries until at most `len` remain.
+    ///
+    /// Entries which are in use are kept, even if that exceeds `len`.
+    fn evict(&mut self, len: usize) {
+        while self.entries.len() > len {
+            let lru = self
+                .entries
+                .iter_mut()
+                .filter_map(|(key, entry)| {
+                    entry.subscribers.retain(|s| !s.is_closed());
+                    let in_use = entry.in_flight || !entry.subscribers.is_empty();
+                    match in_use {
+                        true => None,
+                        false => Some((entry.used_at, key)),
+                    }
+                })
+                .min_by(|a, b| a.0.total_cmp(&b.0))
+                .map(|(_, key)| key.clone());
+            match lru {
+                Some(key) => self.entries.remove(&key),
+                None => return,
+            };
+        }
+    }
+}
+
+/// The key a response is cached under.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+struct QueryKey {
+    method: String,
+    url: String,
+}
+
+impl QueryKey {
+    /// Create a new instance. Methods are case-insensitive, so they're
+    /// normalized to uppercase.
+    fn new(req: &Request) -> Self {
+        Self {
+            method: req.method().to_ascii_uppercase(),
+            url: req.url().to_owned(),
+        }
+    }
+
+    /// Returns true if responses to the request can be cached.
+    fn is_cacheable(&self) -> bool {
+        matches!(self.method.as_str(), "GET" | "HEAD")
+    }
+}
+
+/// The cached state for a single key.
+#[derive(Debug)]
+struct Entry {
+    /// The request used to refresh this entry.
+    request: Request,
+    /// The most recent successful response.
+    snapshot: Option<Snapshot>,
+    /// When the snapshot was taken, in milliseconds since the epoch.
+    fetched_at: f64,
+    /// When the entry was last requested, in milliseconds since the epoch.
+    used_at: f64,
+    /// Whether the entry was explicitly invalidated.
+    invalidated: bool,
+    /// Whether a request for this entry is currently in flight.
+    in_flight: bool,
+    /// Callers waiting for the in-flight request to complete.
+    waiters: Vec<oneshot::Sender<io::Result<Snapshot>>>,
+    /// Streams which receive every update to this entry.
+    subscribers: Vec<Sender<io::Result<Response>>>,
+}
+
+impl Entry {
+    fn new(request: Request) -> Self {
+        Self {
+            request,
+            snapshot: None,
+            fetched_at: 0.0,
+            used_at: 0.0,
+            invalidated: false,
+            in_flight: false,
+            waiters: vec![],
+            subscribers: vec![],
+        }
+    }
+
+    fn is_stale(&self, stale_time: Duration) -> bool {
+        let elapsed = js_sys::Date::now() - self.fetched_at;
+        self.invalidated || elapsed >= stale_time.as_millis() as f64
+    }
+}
+
+impl QueryCache {
+    /// Create a new instance of `QueryCache`.
+    ///
+    /// Cached responses are considered stale right away, which means every
+    /// fetch serves the cached response and revalidates it in the background.
+    pub fn new() -> Self {
+        Self::with_stale_time(Duration::ZERO)
+    }
+
+    /// Create a new instance of `QueryCache` which considers cached responses
+    /// fresh for the given duration.
+    ///
+    /// Fresh responses are served from the cache without being revalidated.
+    pub fn with_stale_time(stale_time: Duration) -> Self {
+        let inner = Inner {
+            stale_time,
+            max_entries: DEFAULT_MAX_ENTRIES,
+            entries: HashMap::new(),
+        };
+        Self {
+            inner: Rc::new(RefCell::new(inner)),
+        }
+    }
+
+    /// Set the maximum number of responses to keep, evicting the least
+    /// recently used entries if there are more.
+    ///
+    /// Entries with a request in flight or an active subscriber are never
+    /// evicted, so the cache may temporarily hold more. Defaults to `100`.
+    pub fn set_max_entries(&self, max_entries: usize) {
+        let max_entries = max_entries.max(1);
+        let mut inner = self.inner.borrow_mut();
+        inner.max_entries = max_entries;
+        inner.evict(max_entries);
+    }
+
+    /// Send a request through the cache.
+    ///
+    /// Only `GET` and `HEAD` requests are cached and deduplicated; requests with
+    /// any other method are sent straight to the network.
+    ///
+    /// # Errors
+    ///
+    /// An error will be returned if no response was cached, and the request
+    /// failed.
+    pub async fn fetch(&self, req: Request) -> io::Result<Response> {
+        let key = QueryKey::new(&req);
+        if !key.is_cacheable() {
+            return req.send().await;
+        }
+
+        let receiver = {
+            let mut inner = self.inner.borrow_mut();
+            let stale_time = inner.stale_time;
+            let entry = inner.entry(&key, &req);
+
+            match &entry.snapshot {
+                Some(snapshot) => {
+                    let res = snapshot.to_response();
+                    let is_stale = entry.is_stale(stale_time);
+                    drop(inner);
+                    if is_stale {
+                        self.revalidate(key, req);
+                    }
+                    return Ok(res);
+                }
+                None => {
+                    let (sender, receiver) = oneshot::channel();
+                    entry.waiters.push(sender);
+                    receiver
+                }
+            }
+        };
+
+        self.revalidate(key, req);
+        match receiver.await {
+            Ok(res) => res.map(|snapshot| snapshot.to_response()),
+            Err(_) => Err(io::Error::new(
+                io::ErrorKind::Interrupted,
+                "QueryCache: request was dropped",
+            )),
+        }
+    }
+
+    /// Create a stream which yields a response every time the entry for a
+    /// request is updated.
+    ///
+    /// If a response was cached it is yielded right away. A request is sent if
+    /// nothing was cached yet, or if the cached response is stale.
+    pub fn subscribe(&self, req: Request) -> QueryStream {
+        let key = QueryKey::new(&req);
+        let (sender, receiver) = channel::unbounded();
+        let needs_fetch = {
+            let mut inner = self.inner.borrow_mut();
+            let stale_time = inner.stale_time;
+            let entry = inner.entry(&key, &req);
+
+            if let Some(snapshot) = &entry.snapshot {
+                let _ = sender.try_send(Ok(snapshot.to_response()));
+            }
+            entry.subscribers.push(sender);
+            entry.snapshot.is_none() || entry.is_stale(stale_time)
+        };
+
+        if needs_fetch {
+            self.revalidate(key, req);
+        }
+        QueryStream { receiver }
+    }
+
+    /// Mark all responses cached for a url as stale.
+    ///
+    /// Entries which have active subscribers are refreshed right away.
+    pub fn invalidate(&self, url: &str) {
+        self.invalidate_where(|key| key.url == url);
+    }
+
+    /// Mark all cached responses as stale.
+    ///
+    /// Entries which have active subscribers are refreshed right away.
+    pub fn invalidate_all(&self) {
+        self.invalidate_where(|_| true);
+    }
+
+    fn invalidate_where(&self, f: impl Fn(&QueryKey) -> bool) {
+        let refresh: Vec<_> = {
+            let mut inner = self.inner.borrow_mut();
+            inner
+                .entries
+                .iter_mut()
+                .filter(|(key, _)| f(key))
+                .filter_map(|(key, entry)| {
+                    entry.invalidated = true;
+                    entry.subscribers.retain(|s| !s.is_closed());
+                    match entry.subscribers.is_empty() {
+                        true => None,
+                        false => Some((key.clone(), entry.request.clone())),
+                    }
+                })
+                .collect()
+        };
+
+        for (key, req) in refresh {
+            self.revalidate(key, req);
+        }
+    }
+
+    /// Send a request in the background, unless one is already in flight.
+    fn revalidate(&self, key: QueryKey, req: Request) {
+        {
+            let mut inner = self.inner.borrow_mut();
+            let entry = match inner.entries.get_mut(&key) {
+                Some(entry) => entry,
+                None => return,
+            };
+            if entry.in_flight {
+                return;
+            }
+            entry.in_flight = true;
+        }
+
+        let this = self.clone();
+        crate::task::spawn_local(async move {
+            let res = match req.send().await {
+                Ok(res) => Snapshot::read(res).await,
+                Err(err) => Err(err),
+            };
+            this.settle(&key, res);
+        });
+    }
+
+    /// Store the outcome of a request, and notify everyone waiting on it.
+    fn settle(&self, key: &QueryKey, res: io::Result<Snapshot>) {
+        let mut inner = self.inner.borrow_mut();
+        let entry = match inner.entries.get_mut(key) {
+            Some(entry) => entry,
+            None => return,
+        };
+
+        entry.in_flight = false;
+        if let Ok(snapshot) = &res {
+            entry.snapshot = Some(snapshot.clone());
+            entry.fetched_at = js_sys::Date::now();
+            entry.invalidated = false;
+        }
+
+        for waiter in entry.waiters.drain(..) {
+            let _ = waiter.send(clone_result(&res));
+        }
+        entry.subscribers.retain(|sender| {
+            let res = clone_result(&res).map(|snapshot| snapshot.to_response());
+            sender.try_send(res).is_ok()
+        });
+    }
+}
+
+impl Default for QueryCache {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// A stream of updates to a cached response.
+///
+/// This `struct` is created by the [`subscribe`] method on [`QueryCache`]. See
+/// its documentation for more.
+///
+/// [`subscribe`]: struct.QueryCache.html#method.subscribe
+/// [`QueryCache`]: struct.QueryCache.html
+#[pin_project]
+#[derive(Debug)]
+#[must_use = "Streams do nothing unless polled"]
+pub struct QueryStream {
+    #[pin]
+    receiver: Receiver<io::Result<Response>>,
+}
+
+impl Stream for QueryStream {
+    type Item = io::Result<Response>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = self.project();
+        this.receiver.poll_next(cx)
+    }
+}
+
+/// A fully read response, from which any number of `Response`s can be created.
+#[derive(Debug, Clone)]
+struct Snapshot {
+    status: u16,
+    status_text: String,
+    headers: Vec<(String, String)>,
+    body: Rc<[u8]>,
+}
+
+impl Snapshot {
+    async fn read(res: Response) -> io::Result<Self> {
+        // Opaque responses report a status of `0`, which can't be used to
+        // construct a new response.
+        if res.status() == 0 {
+            return Err(io::Error::new(
+                io::ErrorKind::Unsupported,
+                "QueryCache: opaque responses cannot be cached",
+            ));
+        }
+
+        let status = res.status();
+        let status_text = res.as_raw().status_text();
+        let headers = res.headers().collect();
+        let body = res.body_bytes().await?.into();
+        Ok(Self {
+            status,
+            status_text,
+            headers,
+            body,
+        })
+    }
+
+    fn to_response(&self) -> Response {
+        let init = web_sys::ResponseInit::new();
+        init.set_status(self.status);
+        init.set_status_text(&self.status_text);
+        let headers = web_sys::Headers::new().unwrap_throw();
+        for (name, value) in &self.headers {
+            headers.append(name, value).unwrap_throw();
+        }
+        init.set_headers_headers(&headers);
+
+        // Null body statuses may not be constructed with a body.
+        let body = match self.status {
+            101 | 204 | 205 | 304 => None,
+            _ => Some(Uint8Array::from(&self.body[..])),
+        };
+        let res = web_sys::Response::new_with_opt_js_u8_array_and_init(body.as_ref(), &init)
+            .unwrap_throw();
+        Response::new(res)
+    }
+}
+
+/// `io::Error` is not `Clone`, so copy its kind and message instead.
+fn clone_result<T: Clone>(res: &io::Result<T>) -> io::Result<T> {
+    match res {
+        Ok(t) => Ok(t.clone()),
+        Err(err) => Err(io::Error::new(err.kind(), err.to_string())),
+    }
+}
@@ -0,0 +1,424 @@
+use std::cell::RefCell;
+use std::collections::VecDeque;
+use std::future::{poll_fn, Future};
+use std::io;
+use std::pin::Pin;
+use std::rc::Rc;
+use std::task::{Context, Poll};
+use std::time::Duration;
+
+use async_channel::{self as channel, Receiver, Sender};
+use futures_core::Stream;
+use futures_timer::Delay;
+use pin_project::pin_project;
+
+use crate::net::{Message, WebSocket};
+
+/// The state of a [`ReconnectingWebSocket`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ConnectionState {
+    /// A connection is being established.
+    Connecting,
+    /// The connection is open.
+    Open,
+    /// The connection was lost, and will be re-established after a delay.
+    Reconnecting {
+        /// The number of consecutive failed connection attempts.
+        attempt: u32,
+        /// How long until the next connection attempt.
+        delay: Duration,
+    },
+    /// The connection was closed, and will not be re-established.
+    Closed,
+}
+
+/// A WebSocket client which survives network interruptions.
+///
+/// When the connection drops it's re-established with exponential backoff.
+/// Messages sent while disconnected are buffered and delivered in order once
+/// the connection is back, after all [subscriptions] have been replayed.
+///
+/// [subscriptions]: #method.subscribe
+///
+/// # Examples
+///
+/// ```no_run
+/// use localghost::prelude::*;
+/// use localghost::{log, net};
+/// use async_std::prelude::*;
+/// use std::time::Duration;
+///
+/// #[localghost::main]
+/// async fn main() {
+///     let mut ws = net::ReconnectingWebSocket::connect("wss://example.com/feed");
+///     ws.subscribe(r#"{"subscribe":"prices"}"#);
+///     ws.set_heartbeat(Duration::from_secs(15), "ping");
+///
+///     let mut states = ws.state_changes();
+///     localghost::task::spawn_local(async move {
+///         while let Some(state) = states.next().await {
+///             log::info!("connection: {:?}", state);
+///         }
+///     });
+///
+///     while let Some(msg) = ws.next().await {
+///         log::info!("message: {:?}", msg);
+///     }
+/// }
+/// ```
+#[pin_project(PinnedDrop)]
+#[derive(Debug)]
+pub struct ReconnectingWebSocket {
+    shared: Rc<RefCell<Shared>>,
+    #[pin]
+    receiver: Receiver<Message>,
+}
+
+#[derive(Debug)]
+struct Shared {
+    url: String,
+    state: ConnectionState,
+    socket: Option<Rc<WebSocket>>,
+    /// Messages sent while disconnected.
+    buffer: VecDeque<Message>,
+    /// Messages sent every time a connection is established.
+    subscriptions: Vec<Message>,
+    heartbeat: Option<(Duration, Message)>,
+    heartbeat_running: bool,
+    /// Tells the current connection that its heartbeat timed out.
+    timed_out: Option<Sender<()>>,
+    /// When the last message was received, in milliseconds since the epoch.
+    last_received: f64,
+    min_backoff: Duration,
+    max_backoff: Duration,
+    closed: bool,
+    messages: Sender<Message>,
+    state_subscribers: Vec<Sender<ConnectionState>>,
+}
+
+impl Shared {
+    fn set_state(&mut self, state: ConnectionState) {
+        self.state = state;
+        self.state_subscribers
+            .retain(|sender| sender.try_send(state).is_ok());
+    }
+
+    /// Start using a newly established connection.
+    fn open(&mut self, socket: Rc<WebSocket>, timed_out: Sender<()>) {
+        for msg in &self.subscriptions {
+            let _ = socket.send(msg.clone());
+        }
+        while let Some(msg) = self.buffer.pop_front() {
+            if socket.send(msg.clone()).is_err() {
+                self.buffer.push_front(msg);
+                break;
+            }
+        }
+        self.last_received = js_sys::Date::now();
+        self.socket = Some(socket);
+        self.timed_out = Some(timed_out);
+        self.set_state(ConnectionState::Open);
+    }
+
+    fn backoff(&self, attempt: u32) -> Duration {
+        let factor = 1 << attempt.min(16);
+        (self.min_backoff * factor).min(self.max_backoff)
+    }
+}
+
+impl ReconnectingWebSocket {
+    /// Create a new instance and start connecting in the background.
+    pub fn connect(url: &str) -> Self {
+        let (messages, receiver) = channel::unbounded();
+        let shared = Rc::new(RefCell::new(Shared {
+            url: url.to_owned(),
+            state: ConnectionState::Connecting,
+            socket: None,
+            buffer: VecDeque::new(),
+            subscriptions: vec![],
+            heartbeat: None,
+            heartbeat_running: false,
+            timed_out: None,
+            last_received: 0.0,
+            min_backoff: Duration::from_millis(500),
+            max_backoff: Duration::from_secs(30),
+            closed: false,
+            messages,
+            state_subscribers: vec![],
+        }));
+
+        crate::task::spawn_local(run(shared.clone()));
+        Self { shared, receiver }
+    }
+
+    /// Get the current connection state.
+    pub fn state(&self) -> ConnectionState {
+        self.shared.borrow().state
+    }
+
+    /// Create a stream of connection state changes.
+    ///
+    /// The current state is yielded first.
+    pub fn state_changes(&self) -> StateStream {
+        let (sender, receiver) = channel::unbounded();
+        let mut shared = self.shared.borrow_mut();
+        let _ = sender.try_send(shared.state);
+        shared.state_subscribers.push(sender);
+        StateStream { receiver }
+    }
+
+    /// Send a message, or buffer it until the connection is open.
+    pub fn send<M>(&self, msg: M)
+    where
+        M: Into<Message>,
+    {
+        let msg = msg.into();
+        let mut shared = self.shared.borrow_mut();
+        let sent = match &shared.socket {
+            Some(socket) => socket.send(msg.clone()).is_ok(),
+            None => false,
+        };
+        if !sent {
+            shared.buffer.push_back(msg);
+        }
+    }
+
+    /// Register a message which is sent every time a connection is
+    /// established, and send it right away if the connection is open.
+    pub fn subscribe<M>(&self, msg: M)
+    where
+        M: Into<Message>,
+    {
+        let msg = msg.into();
+        let mut shared = self.shared.borrow_mut();
+        if let Some(socket) = &shared.socket {
+            let _ = socket.send(msg.clone());
+        }
+        shared.subscriptions.push(msg);
+    }
+
+    /// Remove all messages registered through [`subscribe`](#method.subscribe).
+    pub fn clear_subscriptions(&self) {
+        self.shared.borrow_mut().subscriptions.clear();
+    }
+
+    /// Send a message at a fixed interval while the connection is open.
+    ///
+    /// If no message is received from the server for two intervals in a row,
+    /// the connection is considered lost, and a new one is established right
+    /// away. This expects the server to reply to heartbeats.
+    pub fn set_heartbeat<M>(&self, interval: Duration, msg: M)
+    where
+        M: Into<Message>,
+    {
+        let mut shared = self.shared.borrow_mut();
+        shared.heartbeat = Some((interval, msg.into()));
+        if !shared.heartbeat_running {
+            shared.heartbeat_running = true;
+            crate::task::spawn_local(heartbeat(self.shared.clone()));
+        }
+    }
+
+    /// Stop sending heartbeats.
+    pub fn clear_heartbeat(&self) {
+        self.shared.borrow_mut().heartbeat = None;
+    }
+
+    /// Set the minimum and maximum delay between connection attempts.
+    ///
+    /// The delay starts at `min` and doubles after every failed attempt, up to
+    /// `max`. Defaults to 500 milliseconds and 30 seconds.
+    pub fn set_backoff(&self, min: Duration, max: Duration) {
+        let mut shared = self.shared.borrow_mut();
+        shared.min_backoff = min;
+        shared.max_backoff = max;
+    }
+
+    /// Receive a message from the connection.
+    ///
+    /// # Errors
+    ///
+    /// An error will be returned once the connection has been closed.
+    pub async fn recv(&self) -> io::Result<Message> {
+        self.receiver.recv().await.map_err(|_| {
+            let url = &self.shared.borrow().url;
+            io::Error::new(
+                io::ErrorKind::NotConnected,
+                format!("ReconnectingWebSocket({}): connection closed", url),
+            )
+        })
+    }
+
+    /// Close the connection, and stop reconnecting.
+    pub fn close(&self) {
+        let mut shared = self.shared.borrow_mut();
+        shared.closed = true;
+        if let Some(socket) = shared.socket.take() {
+            socket.close();
+        }
+    }
+}
+
+#[pin_project::pinned_drop]
+impl PinnedDrop for ReconnectingWebSocket {
+    fn drop(self: Pin<&mut Self>) {
+        self.close();
+    }
+}
+
+impl Stream for ReconnectingWebSocket {
+    type Item = Message;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = self.project();
+        this.receiver.poll_next(cx)
+    }
+}
+
+/// Keep a connection open until the instance is closed.
+async fn run(shared: Rc<RefCell<Shared>>) {
+    let mut attempt = 0;
+    loop {
+        let url = {
+            let mut shared = shared.borrow_mut();
+            if shared.closed {
+                break;
+            }
+            shared.set_state(ConnectionState::Connecting);
+            shared.url.clone()
+        };
+
+        if let Ok(socket) = WebSocket::connect(&url).await {
+            attempt = 0;
+            let socket = Rc::new(socket);
+            if shared.borrow().closed {
+                socket.close();
+                break;
+            }
+            let (timeout_sender, timed_out) = channel::bounded(1);
+            shared.borrow_mut().open(socket.clone(), timeout_sender);
+
+            let mut is_dead = false;
+            loop {
+                match recv_until_timeout(&socket, &timed_out).await {
+                    Some(Ok(msg)) => {
+                        let mut shared = shared.borrow_mut();
+                        shared.last_received = js_sys::Date::now();
+                        let _ = shared.messages.try_send(msg);
+                    }
+                    Some(Err(_)) => break,
+                    None => {
+                        is_dead = true;
+                        break;
+                    }
+                }
+            }
+            {
+                let mut shared = shared.borrow_mut();
+                shared.socket = None;
+                shared.timed_out = None;
+            }
+
+            // The closing handshake of a dead connection may take a long
+            // time to complete, so don't wait for it.
+            if is_dead {
+                socket.close();
+                continue;
+            }
+        }
+
+        let delay = {
+            let mut shared = shared.borrow_mut();
+            if shared.closed {
+                break;
+            }
+            attempt += 1;
+            let delay = shared.backoff(attempt - 1);
+            crate::log::debug!(
+                "ReconnectingWebSocket({}): reconnecting in {:?}",
+                shared.url,
+                delay
+            );
+            shared.set_state(ConnectionState::Reconnecting { attempt, delay });
+            delay
+        };
+        Delay::new(delay).await;
+    }
+
+    let mut shared = shared.borrow_mut();
+    shared.set_state(ConnectionState::Closed);
+    shared.messages.close();
+}
+
+/// Send heartbeats until they're disabled, or the instance is closed.
+async fn heartbeat(shared: Rc<RefCell<Shared>>) {
+    loop {
+        let interval = {
+            let mut shared = shared.borrow_mut();
+            match &shared.heartbeat {
+                Some((interval, _)) if !shared.closed => *interval,
+                _ => {
+                    shared.heartbeat_running = false;
+                    return;
+                }
+            }
+        };
+        Delay::new(interval).await;
+
+        let mut shared = shared.borrow_mut();
+        let interval = match (&shared.socket, &shared.heartbeat) {
+            (Some(_), Some((interval, _))) => *interval,
+            _ => continue,
+        };
+        let silence = js_sys::Date::now() - shared.last_received;
+        if silence > 2.0 * interval.as_millis() as f64 {
+            crate::log::debug!("ReconnectingWebSocket({}): heartbeat timed out", shared.url);
+            shared.socket = None;
+            if let Some(timed_out) = shared.timed_out.take() {
+                let _ = timed_out.try_send(());
+            }
+        } else if let (Some(socket), Some((_, msg))) = (&shared.socket, &shared.heartbeat) {
+            let _ = socket.send(msg.clone());
+        }
+    }
+}
+
+/// Receive the next message, or `None` once the heartbeat timed out.
+async fn recv_until_timeout(
+    socket: &WebSocket,
+    timed_out: &Receiver<()>,
+) -> Option<io::Result<Message>> {
+    let mut recv = Box::pin(socket.recv());
+    let mut timeout = Box::pin(timed_out.recv());
+    poll_fn(|cx| {
+        if timeout.as_mut().poll(cx).is_ready() {
+            return Poll::Ready(None);
+        }
+        recv.as_mut().poll(cx).map(Some)
+    })
+    .await
+}
+
+/// A stream of [`ConnectionState`] changes.
+///
+/// This `struct` is created by the [`state_changes`] method on
+/// [`ReconnectingWebSocket`]. See its documentation for more.
+///
+/// [`state_changes`]: struct.ReconnectingWebSocket.html#method.state_changes
+/// [`ReconnectingWebSocket`]: struct.ReconnectingWebSocket.html
+#[pin_project]
+#[derive(Debug)]
+#[must_use = "Streams do nothing unless polled"]
+pub struct StateStream {
+    #[pin]
+    receiver: Receiver<ConnectionState>,
+}
+
+impl Stream for StateStream {
+    type Item = ConnectionState;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = self.project();
+        this.receiver.poll_next(cx)
+    }
+}
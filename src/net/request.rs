@@ -1,10 +1,11 @@
 use std::fmt::Debug;
 use std::io;
 
+use js_sys::Uint8Array;
 use wasm_bindgen::JsCast;
 use wasm_bindgen_futures::JsFuture;
 
-use crate::net::Response;
+use crate::net::{cache, CacheStrategy, Headers, HeadersIter, Response};
 use crate::prelude::*;
 use crate::utils::{self, ResultExt};
 
@@ -14,6 +15,8 @@ pub struct Request {
     method: String,
     headers: web_sys::Headers,
     url: String,
+    body: Option<Vec<u8>>,
+    cache: Option<(String, CacheStrategy)>,
 }
 
 impl Request {
@@ -23,6 +26,8 @@ impl Request {
             method: method.to_owned(),
             url: url.to_owned(),
             headers: web_sys::Headers::new().unwrap_throw(),
+            body: None,
+            cache: None,
         }
     }
 
@@ -71,6 +76,16 @@ impl Request {
         Self::new("TRACE", url)
     }
 
+    /// Get the HTTP method.
+    pub fn method(&self) -> &str {
+        &self.method
+    }
+
+    /// Get the url.
+    pub fn url(&self) -> &str {
+        &self.url
+    }
+
     /// Get a header.
     pub fn header(&self, name: &str) -> Option<String> {
         self.headers.get(name).unwrap_throw()
@@ -95,19 +110,63 @@ impl Request {
         self.headers.delete(name).unwrap_throw();
     }
 
+    /// Get an iterator over all headers.
+    pub fn headers(&self) -> HeadersIter {
+        Headers::new(self.headers.clone()).iter()
+    }
+
+    /// Get the request body, if one was set.
+    pub fn body(&self) -> Option<&[u8]> {
+        self.body.as_deref()
+    }
+
+    /// Set the request body.
+    pub fn set_body<B>(&mut self, body: B)
+    where
+        B: Into<Vec<u8>>,
+    {
+        self.body = Some(body.into());
+    }
+
+    /// Serve this request through a [`Cache`](crate::net::Cache) using the
+    /// given strategy.
+    ///
+    /// Only `GET` requests are cached; requests with any other method go
+    /// straight to the network. Responses marked `Cache-Control: no-store` are
+    /// never written to the cache.
+    pub fn set_cache_strategy(&mut self, cache_name: &str, strategy: CacheStrategy) {
+        self.cache = Some((cache_name.to_owned(), strategy));
+    }
+
     /// Submit a request
     ///
     /// # Errors
     ///
     /// An error may be returned if the underlying connection returns an error.
-    pub async fn send(self) -> Result<Response, io::Error> {
+    /// An error of kind `io::ErrorKind::InvalidInput` is returned if the
+    /// request is invalid, such as a `GET` or `HEAD` request with a body.
+    pub async fn send(mut self) -> Result<Response, io::Error> {
+        match self.cache.take() {
+            Some((name, strategy)) if self.method.eq_ignore_ascii_case("GET") => {
+                cache::send(self, &name, strategy).await
+            }
+            _ => self.fetch().await,
+        }
+    }
+
+    /// Perform the `fetch` call without consulting any caches.
+    pub(crate) async fn fetch(&self) -> io::Result<Response> {
         // Initialize the request config.
-        let mut init = web_sys::RequestInit::new();
-        init.method(&self.method);
-        init.headers(self.headers.as_ref());
+        let init = web_sys::RequestInit::new();
+        init.set_method(&self.method);
+        init.set_headers_headers(&self.headers);
+        if let Some(body) = &self.body {
+            init.set_body(&Uint8Array::from(body.as_slice()));
+        }
 
         // Send the request.
-        let req = web_sys::Request::new_with_str_and_init(&self.url, &init).unwrap_throw();
+        let req = web_sys::Request::new_with_str_and_init(&self.url, &init)
+            .err_kind(io::ErrorKind::InvalidInput)?;
         let fut = JsFuture::from(utils::window().fetch_with_request(&req));
         let res = fut.await.err_kind(io::ErrorKind::Other)?;
         debug_assert!(res.is_instance_of::<web_sys::Response>());
@@ -115,3 +174,17 @@ impl Request {
         Ok(Response::new(res.dyn_into().unwrap_throw()))
     }
 }
+
+impl Clone for Request {
+    /// Clone the request, copying its headers so the two instances can be
+    /// modified independently.
+    fn clone(&self) -> Self {
+        Self {
+            method: self.method.clone(),
+            url: self.url.clone(),
+            headers: web_sys::Headers::new_with_headers(&self.headers).unwrap_throw(),
+            body: self.body.clone(),
+            cache: self.cache.clone(),
+        }
+    }
+}
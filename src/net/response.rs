@@ -24,6 +24,22 @@ impl Response {
         Self { inner, headers }
     }
 
+    /// Access the raw `web_sys::Response`.
+    pub(crate) fn as_raw(&self) -> &web_sys::Response {
+        &self.inner
+    }
+
+    /// Create a copy of the response, including a copy of its body.
+    ///
+    /// # Errors
+    ///
+    /// An error will be returned if the body of the response has already been
+    /// read.
+    pub fn try_clone(&self) -> io::Result<Self> {
+        let res = self.inner.clone().err_kind(io::ErrorKind::Other)?;
+        Ok(Self::new(res))
+    }
+
     /// Get the HTTP return status code.
     pub fn status(&self) -> u16 {
         self.inner.status()
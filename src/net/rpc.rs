@@ -0,0 +1,594 @@
+use std::cell::{Cell, RefCell};
+use std::collections::HashMap;
+use std::fmt::{self, Debug, Display};
+use std::future::{poll_fn, Future};
+use std::io;
+use std::pin::Pin;
+use std::rc::Rc;
+use std::task::{Context, Poll};
+use std::time::Duration;
+
+use async_channel::{self as channel, Receiver, Sender};
+use futures_channel::oneshot;
+use futures_core::Stream;
+use futures_timer::Delay;
+use pin_project::pin_project;
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+
+use crate::net::{BroadcastChannel, Message, MessagePort, ReconnectingWebSocket, WebSocket};
+
+/// A boxed future which is not `Send`.
+type LocalBoxFuture<'a, T> = Pin<Box<dyn Future<Output = T> + 'a>>;
+
+/// A handler for incoming requests.
+type Handler = Rc<dyn Fn(Value) -> LocalBoxFuture<'static, Result<Value, RpcError>>>;
+
+/// A channel over which [`Rpc`] messages can be exchanged.
+pub trait Transport {
+    /// Send a text message.
+    fn send_text(&self, text: &str) -> io::Result<()>;
+
+    /// Receive the next text message.
+    ///
+    /// An error should be returned once the transport has been closed.
+    fn recv_text(&self) -> LocalBoxFuture<'_, io::Result<String>>;
+
+    /// Returns `true` if messages are delivered to every other peer on the
+    /// transport, rather than to a single remote peer.
+    ///
+    /// Peers on a broadcast transport don't respond to messages they can't
+    /// handle, since those are likely meant for another peer. Defaults to
+    /// `false`.
+    fn is_broadcast(&self) -> bool {
+        false
+    }
+}
+
+impl Transport for WebSocket {
+    fn send_text(&self, text: &str) -> io::Result<()> {
+        self.send(text)
+    }
+
+    fn recv_text(&self) -> LocalBoxFuture<'_, io::Result<String>> {
+        Box::pin(async move {
+            loop {
+                if let Message::Text(text) = self.recv().await? {
+                    return Ok(text);
+                }
+            }
+        })
+    }
+}
+
+impl Transport for ReconnectingWebSocket {
+    fn send_text(&self, text: &str) -> io::Result<()> {
+        self.send(text);
+        Ok(())
+    }
+
+    fn recv_text(&self) -> LocalBoxFuture<'_, io::Result<String>> {
+        Box::pin(async move {
+            loop {
+                if let Message::Text(text) = self.recv().await? {
+                    return Ok(text);
+                }
+            }
+        })
+    }
+}
+
+impl Transport for MessagePort {
+    fn send_text(&self, text: &str) -> io::Result<()> {
+        self.send(text)
+    }
+
+    fn recv_text(&self) -> LocalBoxFuture<'_, io::Result<String>> {
+        Box::pin(self.recv())
+    }
+}
+
+impl Transport for BroadcastChannel {
+    fn send_text(&self, text: &str) -> io::Result<()> {
+        self.send(text)
+    }
+
+    fn recv_text(&self) -> LocalBoxFuture<'_, io::Result<String>> {
+        Box::pin(self.recv())
+    }
+
+    fn is_broadcast(&self) -> bool {
+        true
+    }
+}
+
+/// The sender of a pending call's result.
+type Pending = RefCell<HashMap<String, oneshot::Sender<Result<Value, RpcError>>>>;
+
+/// A JSON-RPC 2.0 peer.
+///
+/// An `Rpc` can both call methods on the remote peer, and serve requests from
+/// it. Calls resolve once the matching response arrives; dropping the future
+/// returned by [`call`] cancels the call. Notifications sent by the remote peer
+/// are yielded by the [`notifications`] stream. Batch requests are not
+/// supported.
+///
+/// Call ids are prefixed with a random id unique to each `Rpc`, so that many
+/// peers can share a [`BroadcastChannel`] without mixing up their responses.
+/// On such a transport, requests for methods without a handler are ignored
+/// rather than answered with an error, since another peer may handle them.
+///
+/// [`call`]: #method.call
+/// [`notifications`]: #method.notifications
+///
+/// [Read more](https://www.jsonrpc.org/specification)
+///
+/// # Examples
+///
+/// ```no_run
+/// use localghost::prelude::*;
+/// use localghost::{log, net};
+/// use localghost::net::{Rpc, RpcError};
+/// use std::io;
+/// use std::time::Duration;
+///
+/// #[localghost::main]
+/// async fn main() -> io::Result<()> {
+///     let ws = net::WebSocket::connect("wss://example.com/rpc").await?;
+///     let rpc = Rpc::new(ws);
+///
+///     // Serve requests from the remote peer.
+///     rpc.handle("ping", |_: ()| async { Ok::<_, RpcError>("pong") });
+///
+///     // Call a method on the remote peer.
+///     let timeout = Duration::from_secs(5);
+///     let sum: i64 = rpc.call_with_timeout("add", [1, 2], timeout).await?;
+///     log::info!("1 + 2 = {}", sum);
+///     Ok(())
+/// }
+/// ```
+pub struct Rpc<T> {
+    shared: Rc<Shared<T>>,
+    _shutdown: oneshot::Sender<()>,
+}
+
+struct Shared<T> {
+    transport: T,
+    id_prefix: String,
+    next_id: Cell<u64>,
+    pending: Pending,
+    handlers: RefCell<HashMap<String, Handler>>,
+    subscribers: RefCell<Vec<Sender<Notification>>>,
+}
+
+impl<T> Debug for Rpc<T>
+where
+    T: Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Rpc")
+            .field("transport", &self.shared.transport)
+            .field("pending", &self.shared.pending.borrow().len())
+            .finish()
+    }
+}
+
+impl<T> Rpc<T>
+where
+    T: Transport + 'static,
+{
+    /// Create a new instance, and start receiving messages from the transport.
+    pub fn new(transport: T) -> Self {
+        let random = || (js_sys::Math::random() * u32::MAX as f64) as u32;
+        let shared = Rc::new(Shared {
+            transport,
+            id_prefix: format!("{:08x}{:08x}", random(), random()),
+            next_id: Cell::new(0),
+            pending: RefCell::new(HashMap::new()),
+            handlers: RefCell::new(HashMap::new()),
+            subscribers: RefCell::new(vec![]),
+        });
+        let (sender, receiver) = oneshot::channel();
+        crate::task::spawn_local(receive(shared.clone(), receiver));
+        Self {
+            shared,
+            _shutdown: sender,
+        }
+    }
+
+    /// Call a method on the remote peer, and wait for its result.
+    ///
+    /// Dropping the returned future cancels the call; any response which
+    /// arrives afterwards is ignored.
+    ///
+    /// # Errors
+    ///
+    /// An error of kind `io::ErrorKind::Other` wrapping an [`RpcError`] is
+    /// returned if the remote peer responds with an error. An error is also
+    /// returned if the transport is closed, or if the params or result could
+    /// not be converted.
+    pub async fn call<P, R>(&self, method: &str, params: P) -> io::Result<R>
+    where
+        P: Serialize,
+        R: DeserializeOwned,
+    {
+        let n = self.shared.next_id.get();
+        self.shared.next_id.set(n + 1);
+        let id = format!("{}:{}", self.shared.id_prefix, n);
+
+        let mut msg = json!({ "jsonrpc": "2.0", "method": method, "id": id });
+        insert_params(&mut msg, params)?;
+
+        let (sender, receiver) = oneshot::channel();
+        self.shared.pending.borrow_mut().insert(id.clone(), sender);
+        let _guard = PendingGuard {
+            pending: &self.shared.pending,
+            id,
+        };
+        self.shared.transport.send_text(&msg.to_string())?;
+
+        match receiver.await {
+            Ok(Ok(value)) => serde_json::from_value(value).map_err(invalid_data),
+            Ok(Err(err)) => Err(io::Error::other(err)),
+            Err(_) => Err(io::Error::new(
+                io::ErrorKind::NotConnected,
+                "Rpc: transport closed",
+            )),
+        }
+    }
+
+    /// Call a method on the remote peer, and wait at most `timeout` for its
+    /// result.
+    ///
+    /// # Errors
+    ///
+    /// An error of kind `io::ErrorKind::TimedOut` is returned if no response
+    /// arrived in time. See [`call`](#method.call) for other errors.
+    pub async fn call_with_timeout<P, R>(
+        &self,
+        method: &str,
+        params: P,
+        timeout: Duration,
+    ) -> io::Result<R>
+    where
+        P: Serialize,
+        R: DeserializeOwned,
+    {
+        let mut call = Box::pin(self.call(method, params));
+        let mut delay = Delay::new(timeout);
+        poll_fn(|cx| {
+            if let Poll::Ready(res) = call.as_mut().poll(cx) {
+                return Poll::Ready(res);
+            }
+            match Pin::new(&mut delay).poll(cx) {
+                Poll::Ready(()) => Poll::Ready(Err(io::Error::new(
+                    io::ErrorKind::TimedOut,
+                    format!("Rpc: call to `{}` timed out", method),
+                ))),
+                Poll::Pending => Poll::Pending,
+            }
+        })
+        .await
+    }
+
+    /// Send a notification to the remote peer.
+    ///
+    /// Notifications don't receive a response.
+    pub fn notify<P>(&self, method: &str, params: P) -> io::Result<()>
+    where
+        P: Serialize,
+    {
+        let mut msg = json!({ "jsonrpc": "2.0", "method": method });
+        insert_params(&mut msg, params)?;
+        self.shared.transport.send_text(&msg.to_string())
+    }
+
+    /// Serve requests for a method.
+    ///
+    /// The handler receives the deserialized params, and its result is sent
+    /// back to the remote peer. Requests whose params cannot be deserialized
+    /// receive an "invalid params" error. Registering a handler for a method
+    /// replaces any previous handler.
+    pub fn handle<P, R, F, Fut>(&self, method: &str, f: F)
+    where
+        P: DeserializeOwned,
+        R: Serialize,
+        F: Fn(P) -> Fut + 'static,
+        Fut: Future<Output = Result<R, RpcError>> + 'static,
+    {
+        let handler: Handler = Rc::new(move |params| match serde_json::from_value(params) {
+            Ok(params) => {
+                let fut = f(params);
+                Box::pin(async move {
+                    let res = fut.await?;
+                    serde_json::to_value(res)
+                        .map_err(|err| RpcError::internal_error().with_data(err.to_string()))
+                })
+            }
+            Err(err) => {
+                let err = RpcError::invalid_params().with_data(err.to_string());
+                Box::pin(async move { Err(err) })
+            }
+        });
+        self.shared
+            .handlers
+            .borrow_mut()
+            .insert(method.to_owned(), handler);
+    }
+
+    /// Create a stream of the notifications sent by the remote peer.
+    pub fn notifications(&self) -> Notifications {
+        let (sender, receiver) = channel::unbounded();
+        self.shared.subscribers.borrow_mut().push(sender);
+        Notifications { receiver }
+    }
+}
+
+impl<T> Shared<T>
+where
+    T: Transport + 'static,
+{
+    /// Handle an incoming message.
+    fn dispatch(self: &Rc<Self>, text: &str) {
+        let msg: Value = match serde_json::from_str(text) {
+            Ok(msg) => msg,
+            Err(_) => return self.respond_error(Value::Null, RpcError::parse_error()),
+        };
+
+        let method = msg.get("method").and_then(Value::as_str);
+        let id = msg.get("id").cloned();
+        match (method, id) {
+            // Request
+            (Some(method), Some(id)) => {
+                let handler = self.handlers.borrow().get(method).cloned();
+                let handler = match handler {
+                    Some(handler) => handler,
+                    None => return self.respond_error(id, RpcError::method_not_found()),
+                };
+                let params = msg.get("params").cloned().unwrap_or(Value::Null);
+                let this = self.clone();
+                crate::task::spawn_local(async move {
+                    let res = handler(params).await;
+                    this.respond(id, res);
+                });
+            }
+            // Notification
+            (Some(method), None) => {
+                let notification = Notification {
+                    method: method.to_owned(),
+                    params: msg.get("params").cloned().unwrap_or(Value::Null),
+                };
+                self.subscribers
+                    .borrow_mut()
+                    .retain(|sender| sender.try_send(notification.clone()).is_ok());
+            }
+            // Response
+            (None, Some(id)) => {
+                // Only resolve calls this instance made. On a broadcast
+                // transport we also see the responses meant for other peers.
+                let sender = match id.as_str() {
+                    Some(id) => self.pending.borrow_mut().remove(id),
+                    None => None,
+                };
+                let sender = match sender {
+                    Some(sender) => sender,
+                    None => return,
+                };
+                let res = match (msg.get("result"), msg.get("error")) {
+                    (_, Some(err)) => Err(serde_json::from_value(err.clone())
+                        .unwrap_or_else(|_| RpcError::internal_error())),
+                    (Some(result), None) => Ok(result.clone()),
+                    (None, None) => Err(RpcError::invalid_request()),
+                };
+                let _ = sender.send(res);
+            }
+            (None, None) => self.respond_error(Value::Null, RpcError::invalid_request()),
+        }
+    }
+
+    /// Respond with an error about a message we couldn't handle. The message
+    /// may be meant for another peer on a broadcast transport, so it's left
+    /// unanswered there.
+    fn respond_error(&self, id: Value, err: RpcError) {
+        if !self.transport.is_broadcast() {
+            self.respond(id, Err(err));
+        }
+    }
+
+    /// Send a response to the remote peer.
+    fn respond(&self, id: Value, res: Result<Value, RpcError>) {
+        let msg = match res {
+            Ok(result) => json!({ "jsonrpc": "2.0", "result": result, "id": id }),
+            Err(err) => json!({ "jsonrpc": "2.0", "error": err, "id": id }),
+        };
+        if let Err(err) = self.transport.send_text(&msg.to_string()) {
+            crate::log::debug!("Rpc: could not send response: {}", err);
+        }
+    }
+}
+
+/// Receive messages until the transport closes, or the `Rpc` is dropped.
+async fn receive<T>(shared: Rc<Shared<T>>, mut shutdown: oneshot::Receiver<()>)
+where
+    T: Transport + 'static,
+{
+    loop {
+        let mut recv = shared.transport.recv_text();
+        let msg = poll_fn(|cx| {
+            if Pin::new(&mut shutdown).poll(cx).is_ready() {
+                return Poll::Ready(None);
+            }
+            recv.as_mut().poll(cx).map(Some)
+        })
+        .await;
+        drop(recv);
+
+        match msg {
+            Some(Ok(text)) => shared.dispatch(&text),
+            _ => break,
+        }
+    }
+
+    // Dropping the senders resolves all pending calls with an error.
+    shared.pending.borrow_mut().clear();
+    shared.subscribers.borrow_mut().clear();
+}
+
+/// Removes a pending call when the call completes or is cancelled.
+struct PendingGuard<'a> {
+    pending: &'a Pending,
+    id: String,
+}
+
+impl Drop for PendingGuard<'_> {
+    fn drop(&mut self) {
+        self.pending.borrow_mut().remove(&self.id);
+    }
+}
+
+/// Add the `params` field to a message, unless the params are empty.
+fn insert_params<P: Serialize>(msg: &mut Value, params: P) -> io::Result<()> {
+    let params = serde_json::to_value(params).map_err(invalid_data)?;
+    if !params.is_null() {
+        msg["params"] = params;
+    }
+    Ok(())
+}
+
+fn invalid_data(err: serde_json::Error) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, err)
+}
+
+/// A notification sent by the remote peer of an [`Rpc`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct Notification {
+    method: String,
+    params: Value,
+}
+
+impl Notification {
+    /// Get the name of the method.
+    pub fn method(&self) -> &str {
+        &self.method
+    }
+
+    /// Get the raw params.
+    pub fn params(&self) -> &Value {
+        &self.params
+    }
+
+    /// Deserialize the params.
+    ///
+    /// # Errors
+    ///
+    /// An error of kind `io::ErrorKind::InvalidData` is returned if the params
+    /// could not be deserialized.
+    pub fn parse_params<P>(&self) -> io::Result<P>
+    where
+        P: DeserializeOwned,
+    {
+        P::deserialize(&self.params).map_err(invalid_data)
+    }
+}
+
+/// A stream of [`Notification`]s.
+///
+/// This `struct` is created by the [`notifications`] method on [`Rpc`]. See
+/// its documentation for more.
+///
+/// [`notifications`]: struct.Rpc.html#method.notifications
+/// [`Rpc`]: struct.Rpc.html
+#[pin_project]
+#[derive(Debug)]
+#[must_use = "Streams do nothing unless polled"]
+pub struct Notifications {
+    #[pin]
+    receiver: Receiver<Notification>,
+}
+
+impl Stream for Notifications {
+    type Item = Notification;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = self.project();
+        this.receiver.poll_next(cx)
+    }
+}
+
+/// A JSON-RPC error object.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RpcError {
+    code: i64,
+    message: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    data: Option<Value>,
+}
+
+impl RpcError {
+    /// Create a new error.
+    pub fn new(code: i64, message: &str) -> Self {
+        Self {
+            code,
+            message: message.to_owned(),
+            data: None,
+        }
+    }
+
+    /// Attach additional data to the error.
+    pub fn with_data<D>(mut self, data: D) -> Self
+    where
+        D: Into<Value>,
+    {
+        self.data = Some(data.into());
+        self
+    }
+
+    /// The message could not be parsed as JSON.
+    pub fn parse_error() -> Self {
+        Self::new(-32700, "Parse error")
+    }
+
+    /// The message is not a valid request.
+    pub fn invalid_request() -> Self {
+        Self::new(-32600, "Invalid Request")
+    }
+
+    /// The method does not exist.
+    pub fn method_not_found() -> Self {
+        Self::new(-32601, "Method not found")
+    }
+
+    /// The params are invalid for the method.
+    pub fn invalid_params() -> Self {
+        Self::new(-32602, "Invalid params")
+    }
+
+    /// An internal error occurred.
+    pub fn internal_error() -> Self {
+        Self::new(-32603, "Internal error")
+    }
+
+    /// Get the error code.
+    pub fn code(&self) -> i64 {
+        self.code
+    }
+
+    /// Get the error message.
+    pub fn message(&self) -> &str {
+        &self.message
+    }
+
+    /// Get the additional data attached to the error.
+    pub fn data(&self) -> Option<&Value> {
+        self.data.as_ref()
+    }
+}
+
+impl Display for RpcError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} ({})", self.message, self.code)
+    }
+}
+
+impl std::error::Error for RpcError {}
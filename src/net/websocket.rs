@@ -0,0 +1,263 @@
+use crate::events::EventListener;
+use crate::prelude::*;
+use crate::utils::ResultExt;
+
+use async_channel::{self as channel, Receiver};
+use futures_core::Stream;
+use js_sys::{ArrayBuffer, Uint8Array};
+use wasm_bindgen::JsCast;
+
+use std::{io, pin::Pin, task::Poll};
+
+/// The state of a [`WebSocket`] connection.
+#[derive(Debug, Clone, Copy, Ord, PartialOrd, Eq, PartialEq, Hash)]
+pub enum WebSocketState {
+    /// The connection is connecting.
+    Connecting,
+    /// The connection is open.
+    Open,
+    /// The closing handshake is in progress.
+    Closing,
+    /// The connection is closed.
+    Closed,
+}
+
+/// A message sent or received over a [`WebSocket`].
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum Message {
+    /// A UTF-8 text message.
+    Text(String),
+    /// A binary message.
+    Binary(Vec<u8>),
+}
+
+impl Message {
+    /// Create a new instance from a raw `MessageEvent`.
+    fn from_raw(ev: &web_sys::MessageEvent) -> Self {
+        let data = ev.data();
+        match data.as_string() {
+            Some(text) => Message::Text(text),
+            None => {
+                let buf: ArrayBuffer = data.dyn_into().unwrap_throw();
+                Message::Binary(Uint8Array::new(&buf).to_vec())
+            }
+        }
+    }
+}
+
+impl From<String> for Message {
+    fn from(text: String) -> Self {
+        Message::Text(text)
+    }
+}
+
+impl From<&str> for Message {
+    fn from(text: &str) -> Self {
+        Message::Text(text.to_owned())
+    }
+}
+
+impl From<Vec<u8>> for Message {
+    fn from(bytes: Vec<u8>) -> Self {
+        Message::Binary(bytes)
+    }
+}
+
+/// A WebSocket client connection.
+///
+/// The stream of incoming messages ends once the connection is closed. If the
+/// connection was not closed cleanly an error is yielded first.
+///
+/// [Read more](https://developer.mozilla.org/en-US/docs/Web/API/WebSocket)
+///
+/// # Examples
+///
+/// ```no_run
+/// use localghost::prelude::*;
+/// use localghost::{log, net};
+/// use async_std::prelude::*;
+///
+/// use std::io;
+///
+/// #[localghost::main]
+/// async fn main() -> io::Result<()> {
+///     let mut ws = net::WebSocket::connect("wss://echo.websocket.org").await?;
+///     ws.send("hello")?;
+///
+///     while let Some(msg) = ws.next().await.transpose()? {
+///         log::info!("message: {:?}", msg);
+///     }
+///     Ok(())
+/// }
+/// ```
+#[pin_project::pin_project(PinnedDrop)]
+#[derive(Debug)]
+pub struct WebSocket {
+    /// The internal `WebSocket` handle.
+    inner: web_sys::WebSocket,
+    /// The url we connect to.
+    url: String,
+    /// Listeners for messages and for the connection closing.
+    _listeners: Vec<EventListener>,
+    /// Message receiver.
+    #[pin]
+    receiver: Receiver<io::Result<Message>>,
+}
+
+impl WebSocket {
+    /// Create a new instance of `WebSocket` and wait for a connection to be
+    /// established.
+    ///
+    /// # Errors
+    ///
+    /// An error will be returned if the url is invalid, or if the connection
+    /// could not be established.
+    pub async fn connect(url: &str) -> io::Result<Self> {
+        crate::log::debug!("WebSocket({}): connection initiated", url);
+
+        let url = url.to_owned();
+        let inner = web_sys::WebSocket::new(&url).err_kind(io::ErrorKind::InvalidInput)?;
+        inner.set_binary_type(web_sys::BinaryType::Arraybuffer);
+
+        // Wait for the connection to either open or close.
+        let (sender, receiver) = channel::bounded(1);
+        let sender2 = sender.clone();
+        let _open = inner.once_with("open", move |_| {
+            let _ = sender2.try_send(true);
+        });
+        let _close = inner.once_with("close", move |_| {
+            let _ = sender.try_send(false);
+        });
+        if !receiver.recv().await.unwrap_or(false) {
+            return Err(io::Error::new(
+                io::ErrorKind::ConnectionRefused,
+                format!("WebSocket({})", url),
+            ));
+        }
+
+        let (sender, receiver) = channel::unbounded();
+        let sender2 = sender.clone();
+        let message_listener = EventListener::listen(&inner, "message", move |ev| {
+            let ev = ev
+                .into_raw()
+                .dyn_into::<web_sys::MessageEvent>()
+                .unwrap_throw();
+            let _ = sender.try_send(Ok(Message::from_raw(&ev)));
+        });
+
+        let url2 = url.clone();
+        let close_listener = EventListener::listen(&inner, "close", move |ev| {
+            let ev = ev
+                .into_raw()
+                .dyn_into::<web_sys::CloseEvent>()
+                .unwrap_throw();
+            crate::log::debug!("WebSocket({}): closed with code {}", url2, ev.code());
+            if !ev.was_clean() {
+                let _ = sender2.try_send(Err(io::Error::new(
+                    io::ErrorKind::ConnectionReset,
+                    format!("WebSocket({}): {}", url2, ev.reason()),
+                )));
+            }
+            sender2.close();
+        });
+
+        crate::log::debug!("WebSocket({}): connection established", url);
+        Ok(Self {
+            inner,
+            url,
+            _listeners: vec![message_listener, close_listener],
+            receiver,
+        })
+    }
+
+    /// Get the url of the connection.
+    pub fn url(&self) -> &str {
+        &self.url
+    }
+
+    /// Get the sub-protocol selected by the server.
+    pub fn protocol(&self) -> String {
+        self.inner.protocol()
+    }
+
+    /// Send a message.
+    ///
+    /// # Errors
+    ///
+    /// An error will be returned if the connection is not open.
+    pub fn send<M>(&self, msg: M) -> io::Result<()>
+    where
+        M: Into<Message>,
+    {
+        if self.ready_state() != WebSocketState::Open {
+            return Err(io::Error::new(
+                io::ErrorKind::NotConnected,
+                format!("WebSocket({})", self.url),
+            ));
+        }
+
+        match msg.into() {
+            Message::Text(text) => self.inner.send_with_str(&text),
+            Message::Binary(bytes) => self.inner.send_with_u8_array(&bytes),
+        }
+        .err_kind(io::ErrorKind::NotConnected)
+    }
+
+    /// Receive a message from the connection.
+    ///
+    /// # Errors
+    ///
+    /// An error will be returned once the connection has been closed.
+    pub async fn recv(&self) -> io::Result<Message> {
+        match self.receiver.recv().await {
+            Ok(res) => res,
+            Err(_) => Err(io::Error::new(
+                io::ErrorKind::NotConnected,
+                format!("WebSocket({}): connection closed", self.url),
+            )),
+        }
+    }
+
+    /// Access the `WebSocket`'s connection state.
+    pub fn ready_state(&self) -> WebSocketState {
+        match self.inner.ready_state() {
+            web_sys::WebSocket::CONNECTING => WebSocketState::Connecting,
+            web_sys::WebSocket::OPEN => WebSocketState::Open,
+            web_sys::WebSocket::CLOSING => WebSocketState::Closing,
+            web_sys::WebSocket::CLOSED => WebSocketState::Closed,
+            _ => unreachable!("Unknown WebSocket ready state"),
+        }
+    }
+
+    /// Close the connection.
+    pub fn close(&self) {
+        self.inner.close().unwrap_throw();
+    }
+}
+
+#[pin_project::pinned_drop]
+impl PinnedDrop for WebSocket {
+    fn drop(self: Pin<&mut Self>) {
+        let this = self.project();
+        let _ = this.inner.close();
+        crate::log::debug!("WebSocket({}): instance closed", this.url);
+    }
+}
+
+impl AsRef<web_sys::EventTarget> for WebSocket {
+    fn as_ref(&self) -> &web_sys::EventTarget {
+        self.inner.as_ref()
+    }
+}
+
+impl Stream for WebSocket {
+    type Item = io::Result<Message>;
+
+    fn poll_next(
+        self: Pin<&mut Self>,
+        cx: &mut std::task::Context<'_>,
+    ) -> Poll<Option<Self::Item>> {
+        let this = self.project();
+        this.receiver.poll_next(cx)
+    }
+}
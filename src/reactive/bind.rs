@@ -0,0 +1,76 @@
+use crate::dom::{Element, VChildren, VNode};
+use crate::reactive::Effect;
+
+use std::fmt::Display;
+
+/// Bindings which keep an element in sync with signals.
+///
+/// Each binding is an [`Effect`], and stops updating the element when it's
+/// dropped.
+impl Element {
+    /// Keep the text of the element in sync with the output of `f`.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use localghost::dom::{body, Element};
+    /// use localghost::reactive::Signal;
+    ///
+    /// let count = Signal::new(0);
+    /// let p = Element::new("p");
+    /// let count2 = count.clone();
+    /// let _binding = p.bind_text(move || count2.get());
+    /// body().append(&p);
+    /// count.set(1);
+    /// ```
+    pub fn bind_text<F, S>(&self, mut f: F) -> Effect
+    where
+        F: FnMut() -> S + 'static,
+        S: Display,
+    {
+        let el = self.as_raw().clone();
+        Effect::new(move || el.set_text_content(Some(&f().to_string())))
+    }
+
+    /// Keep an attribute in sync with the output of `f`. The attribute is
+    /// removed when `f` returns `None`.
+    pub fn bind_attr<F>(&self, name: &str, mut f: F) -> Effect
+    where
+        F: FnMut() -> Option<String> + 'static,
+    {
+        let el = Element::from_element(self.as_raw().clone());
+        let name = name.to_owned();
+        Effect::new(move || match f() {
+            Some(value) => el.set_attr(&name, &value),
+            None => el.remove_attr(&name),
+        })
+    }
+
+    /// Add the class while `f` returns `true`, and remove it otherwise.
+    pub fn bind_class<F>(&self, class: &str, mut f: F) -> Effect
+    where
+        F: FnMut() -> bool + 'static,
+    {
+        let classes = self.class_list();
+        let class = class.to_owned();
+        Effect::new(move || match f() {
+            true => classes.add(&class),
+            false => classes.remove(&class),
+        })
+    }
+
+    /// Keep the children of the element in sync with the output of `f`.
+    ///
+    /// The children are diffed like in a [`VDom`], so keyed children keep
+    /// their nodes. Any children which weren't created by `f` are removed.
+    ///
+    /// [`VDom`]: crate::dom::VDom
+    pub fn bind_children<F>(&self, mut f: F) -> Effect
+    where
+        F: FnMut() -> Vec<VNode> + 'static,
+    {
+        let el = Element::from_element(self.as_raw().clone());
+        let mut children = VChildren::default();
+        Effect::new(move || children.render(&el, f()))
+    }
+}
@@ -0,0 +1,40 @@
+use crate::reactive::runtime::Computation;
+
+use std::fmt::{self, Debug};
+use std::rc::Rc;
+
+/// A side effect which re-runs when the signals it reads change.
+///
+/// The effect runs once when it's created. Later runs are batched, and
+/// happen when the queue is flushed. See [`Flush`] for more. The effect stops
+/// when it's dropped.
+///
+/// [`Flush`]: crate::reactive::Flush
+#[must_use = "Effects stop running when dropped"]
+pub struct Effect {
+    computation: Rc<Computation>,
+}
+
+impl Effect {
+    /// Create a new instance, and run it right away.
+    pub fn new<F>(f: F) -> Self
+    where
+        F: FnMut() + 'static,
+    {
+        Self {
+            computation: Computation::new(false, Box::new(f)),
+        }
+    }
+}
+
+impl Drop for Effect {
+    fn drop(&mut self) {
+        self.computation.dispose();
+    }
+}
+
+impl Debug for Effect {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Effect").finish()
+    }
+}
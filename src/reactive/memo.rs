@@ -0,0 +1,92 @@
+use crate::prelude::*;
+use crate::reactive::runtime::{untrack, Computation};
+use crate::reactive::Signal;
+
+use std::cell::RefCell;
+use std::fmt::{self, Debug};
+use std::rc::Rc;
+
+/// A value derived from other signals, which is only recomputed when they
+/// change.
+///
+/// Memos are recomputed right away rather than batched, so effects never
+/// see a stale value. Subscribers are only notified when the new value is
+/// different from the old one.
+///
+/// # Examples
+///
+/// ```no_run
+/// use localghost::reactive::{Memo, Signal};
+///
+/// let name = Signal::new(String::from("world"));
+/// let name2 = name.clone();
+/// let greeting = Memo::new(move || format!("Hello, {}!", name2.get()));
+/// name.set(String::from("localghost"));
+/// assert_eq!(greeting.get(), "Hello, localghost!");
+/// ```
+pub struct Memo<T> {
+    signal: Signal<T>,
+    computation: Rc<Computation>,
+}
+
+impl<T: PartialEq + 'static> Memo<T> {
+    /// Create a new instance, and compute its initial value.
+    pub fn new<F>(mut f: F) -> Self
+    where
+        F: FnMut() -> T + 'static,
+    {
+        let slot: Rc<RefCell<Option<Signal<T>>>> = Rc::new(RefCell::new(None));
+        let slot2 = slot.clone();
+        let computation = Computation::new(
+            true,
+            Box::new(move || {
+                let value = f();
+                let signal = slot2.borrow().clone();
+                match signal {
+                    None => *slot2.borrow_mut() = Some(Signal::new(value)),
+                    Some(signal) => {
+                        if untrack(|| signal.with(|old| *old != value)) {
+                            signal.set(value);
+                        }
+                    }
+                }
+            }),
+        );
+        let signal = slot.borrow().clone().unwrap_throw();
+        Self {
+            signal,
+            computation,
+        }
+    }
+
+    /// Get a copy of the current value.
+    pub fn get(&self) -> T
+    where
+        T: Clone,
+    {
+        self.signal.get()
+    }
+
+    /// Access the current value without copying it.
+    pub fn with<F, R>(&self, f: F) -> R
+    where
+        F: FnOnce(&T) -> R,
+    {
+        self.signal.with(f)
+    }
+}
+
+impl<T> Clone for Memo<T> {
+    fn clone(&self) -> Self {
+        Self {
+            signal: self.signal.clone(),
+            computation: self.computation.clone(),
+        }
+    }
+}
+
+impl<T: Debug> Debug for Memo<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Memo").field("value", &self.signal).finish()
+    }
+}
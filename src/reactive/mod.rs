@@ -0,0 +1,48 @@
+//! Fine-grained reactivity.
+//!
+//! A [`Signal`] holds a value, a [`Memo`] derives a value from other signals,
+//! and an [`Effect`] runs a side effect. Memos and effects track the signals
+//! they read, and re-run when those change. Elements can be bound to signals
+//! directly, so that only the parts of the DOM which depend on a signal are
+//! updated when it changes.
+//!
+//! # Examples
+//!
+//! ```no_run
+//! use localghost::prelude::*;
+//! use localghost::dom::{body, Element};
+//! use localghost::reactive::{Memo, Signal};
+//!
+//! #[localghost::main]
+//! async fn main() {
+//!     let count = Signal::new(0);
+//!     let count2 = count.clone();
+//!     let is_even = Memo::new(move || count2.get() % 2 == 0);
+//!
+//!     let button = Element::with_text("button", "+1");
+//!     let count3 = count.clone();
+//!     let _binding = button.bind_class("even", move || is_even.get());
+//!     button
+//!         .on_with("click", move |_| count3.update(|n| *n += 1))
+//!         .forget();
+//!     body().append(&button);
+//!
+//!     let p = Element::new("p");
+//!     let _binding = p.bind_text(move || count.get());
+//!     body().append(&p);
+//!
+//!     // Bindings stop when dropped, so keep them alive.
+//!     std::future::pending::<()>().await;
+//! }
+//! ```
+
+mod bind;
+mod effect;
+mod memo;
+mod runtime;
+mod signal;
+
+pub use effect::Effect;
+pub use memo::Memo;
+pub use runtime::{flush, set_flush, untrack, Flush};
+pub use signal::Signal;
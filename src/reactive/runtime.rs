@@ -0,0 +1,191 @@
+use crate::prelude::*;
+use crate::utils::window;
+
+use wasm_bindgen::closure::Closure;
+use wasm_bindgen::JsCast;
+
+use std::cell::{Cell, RefCell};
+use std::rc::{Rc, Weak};
+
+/// When queued effects are run.
+///
+/// Changes made to signals are batched, and the effects depending on them
+/// only run once the batch is flushed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Flush {
+    /// Flush in a microtask, right after the current task completes.
+    Microtask,
+    /// Flush right before the browser paints the next frame.
+    AnimationFrame,
+}
+
+/// How many times effects may queue each other during a single flush before
+/// the remaining effects are dropped.
+const MAX_FLUSH_ROUNDS: usize = 100;
+
+/// Set when queued effects are run. Defaults to `Flush::Microtask`.
+pub fn set_flush(flush: Flush) {
+    RUNTIME.with(|rt| rt.flush.set(flush));
+}
+
+/// Run all queued effects right away.
+///
+/// Effects which write to signals read by other effects queue those effects
+/// in turn, and they run as part of the same flush. If effects keep queuing
+/// each other in a cycle, the flush gives up after a fixed number of rounds
+/// and drops the remaining effects.
+pub fn flush() {
+    RUNTIME.with(|rt| rt.scheduled.set(false));
+    for _ in 0..MAX_FLUSH_ROUNDS {
+        let batch = RUNTIME.with(|rt| rt.queue.take());
+        if batch.is_empty() {
+            return;
+        }
+        for computation in batch.iter().filter_map(Weak::upgrade) {
+            computation.queued.set(false);
+            computation.run();
+        }
+    }
+
+    let dropped = RUNTIME.with(|rt| rt.queue.take());
+    for computation in dropped.iter().filter_map(Weak::upgrade) {
+        computation.queued.set(false);
+    }
+    if !dropped.is_empty() {
+        crate::log::warn!(
+            "reactive: effects kept queuing each other, dropped {} effects",
+            dropped.len()
+        );
+    }
+}
+
+thread_local! {
+    static RUNTIME: Runtime = const {
+        Runtime {
+            observer: RefCell::new(None),
+            queue: RefCell::new(Vec::new()),
+            scheduled: Cell::new(false),
+            flush: Cell::new(Flush::Microtask),
+        }
+    };
+}
+
+struct Runtime {
+    /// The computation which is currently running, and tracking the signals
+    /// it reads.
+    observer: RefCell<Option<Rc<Computation>>>,
+    /// Effects waiting to be run.
+    queue: RefCell<Vec<Weak<Computation>>>,
+    scheduled: Cell<bool>,
+    flush: Cell<Flush>,
+}
+
+/// Something a computation can depend on.
+pub(crate) trait Source {
+    /// Stop notifying the computation of changes.
+    fn unsubscribe(&self, computation: &Rc<Computation>);
+}
+
+/// A function which is re-run when the signals it read last time change.
+pub(crate) struct Computation {
+    f: RefCell<Box<dyn FnMut()>>,
+    sources: RefCell<Vec<Rc<dyn Source>>>,
+    /// Memos are re-run right away, so effects never see stale values.
+    eager: bool,
+    queued: Cell<bool>,
+    running: Cell<bool>,
+}
+
+impl Computation {
+    /// Create a new computation, and run it for the first time.
+    pub(crate) fn new(eager: bool, f: Box<dyn FnMut()>) -> Rc<Self> {
+        let computation = Rc::new(Self {
+            f: RefCell::new(f),
+            sources: RefCell::new(vec![]),
+            eager,
+            queued: Cell::new(false),
+            running: Cell::new(false),
+        });
+        computation.run();
+        computation
+    }
+
+    /// Run the computation, and track the signals it reads.
+    pub(crate) fn run(self: &Rc<Self>) {
+        // Never re-enter a computation which is already running.
+        let mut f = match self.f.try_borrow_mut() {
+            Ok(f) => f,
+            Err(_) => return,
+        };
+        for source in self.sources.take() {
+            source.unsubscribe(self);
+        }
+        let prev = RUNTIME.with(|rt| rt.observer.replace(Some(self.clone())));
+        self.running.set(true);
+        f();
+        self.running.set(false);
+        RUNTIME.with(|rt| *rt.observer.borrow_mut() = prev);
+    }
+
+    /// Re-run the computation because one of its sources changed.
+    pub(crate) fn notify(self: &Rc<Self>) {
+        // A computation which writes to a signal it reads would otherwise
+        // re-run itself forever. It already sees the value it wrote.
+        if self.running.get() {
+            return;
+        }
+        if self.eager {
+            self.run();
+        } else if !self.queued.replace(true) {
+            RUNTIME.with(|rt| rt.queue.borrow_mut().push(Rc::downgrade(self)));
+            schedule();
+        }
+    }
+
+    /// Stop tracking all sources.
+    pub(crate) fn dispose(self: &Rc<Self>) {
+        for source in self.sources.take() {
+            source.unsubscribe(self);
+        }
+    }
+}
+
+/// Get the running computation, and record that it depends on `source`.
+pub(crate) fn track(source: Rc<dyn Source>) -> Option<Rc<Computation>> {
+    let observer = RUNTIME.with(|rt| rt.observer.borrow().clone())?;
+    observer.sources.borrow_mut().push(source);
+    Some(observer)
+}
+
+/// Run `f` without tracking the signals it reads.
+pub fn untrack<F, R>(f: F) -> R
+where
+    F: FnOnce() -> R,
+{
+    let prev = RUNTIME.with(|rt| rt.observer.take());
+    let res = f();
+    RUNTIME.with(|rt| *rt.observer.borrow_mut() = prev);
+    res
+}
+
+/// Schedule a flush, unless one is scheduled already.
+fn schedule() {
+    let mode = RUNTIME.with(|rt| {
+        if rt.scheduled.replace(true) {
+            return None;
+        }
+        Some(rt.flush.get())
+    });
+    match mode {
+        None => {}
+        Some(Flush::Microtask) => {
+            crate::task::spawn_local(async { flush() });
+        }
+        Some(Flush::AnimationFrame) => {
+            let f = Closure::once_into_js(flush);
+            window()
+                .request_animation_frame(f.unchecked_ref())
+                .unwrap_throw();
+        }
+    }
+}
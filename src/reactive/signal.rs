@@ -0,0 +1,119 @@
+use crate::reactive::runtime::{track, Computation, Source};
+
+use std::cell::RefCell;
+use std::fmt::{self, Debug};
+use std::rc::{Rc, Weak};
+
+/// A value which notifies the computations reading it when it changes.
+///
+/// Reading a signal from within an [`Effect`] or [`Memo`] subscribes it to
+/// the signal. Cloning a signal creates a new handle to the same value.
+///
+/// [`Effect`]: crate::reactive::Effect
+/// [`Memo`]: crate::reactive::Memo
+///
+/// # Examples
+///
+/// ```no_run
+/// use localghost::log;
+/// use localghost::reactive::{Effect, Signal};
+///
+/// let count = Signal::new(0);
+/// let count2 = count.clone();
+/// let _effect = Effect::new(move || log::info!("count: {}", count2.get()));
+/// count.update(|count| *count += 1);
+/// ```
+pub struct Signal<T> {
+    inner: Rc<Inner<T>>,
+}
+
+struct Inner<T> {
+    value: RefCell<T>,
+    subscribers: RefCell<Vec<Weak<Computation>>>,
+}
+
+impl<T: 'static> Signal<T> {
+    /// Create a new instance.
+    pub fn new(value: T) -> Self {
+        Self {
+            inner: Rc::new(Inner {
+                value: RefCell::new(value),
+                subscribers: RefCell::new(vec![]),
+            }),
+        }
+    }
+
+    /// Get a copy of the current value.
+    pub fn get(&self) -> T
+    where
+        T: Clone,
+    {
+        self.with(T::clone)
+    }
+
+    /// Access the current value without copying it.
+    pub fn with<F, R>(&self, f: F) -> R
+    where
+        F: FnOnce(&T) -> R,
+    {
+        if let Some(computation) = track(self.inner.clone()) {
+            let mut subscribers = self.inner.subscribers.borrow_mut();
+            let weak = Rc::downgrade(&computation);
+            if !subscribers.iter().any(|sub| sub.ptr_eq(&weak)) {
+                subscribers.push(weak);
+            }
+        }
+        f(&self.inner.value.borrow())
+    }
+
+    /// Set the value, and notify all subscribers.
+    pub fn set(&self, value: T) {
+        *self.inner.value.borrow_mut() = value;
+        self.notify();
+    }
+
+    /// Modify the value in place, and notify all subscribers.
+    pub fn update<F>(&self, f: F)
+    where
+        F: FnOnce(&mut T),
+    {
+        f(&mut self.inner.value.borrow_mut());
+        self.notify();
+    }
+
+    fn notify(&self) {
+        let subscribers: Vec<_> = {
+            let mut subscribers = self.inner.subscribers.borrow_mut();
+            subscribers.retain(|sub| sub.strong_count() > 0);
+            subscribers.iter().filter_map(Weak::upgrade).collect()
+        };
+        for computation in subscribers {
+            computation.notify();
+        }
+    }
+}
+
+impl<T> Source for Inner<T> {
+    fn unsubscribe(&self, computation: &Rc<Computation>) {
+        let weak = Rc::downgrade(computation);
+        self.subscribers
+            .borrow_mut()
+            .retain(|sub| !sub.ptr_eq(&weak));
+    }
+}
+
+impl<T> Clone for Signal<T> {
+    fn clone(&self) -> Self {
+        Self {
+            inner: self.inner.clone(),
+        }
+    }
+}
+
+impl<T: Debug> Debug for Signal<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Signal")
+            .field("value", &self.inner.value.borrow())
+            .finish()
+    }
+}
@@ -0,0 +1,235 @@
+use crate::net::BroadcastChannel;
+use crate::task::{lock, spawn_local, Lock};
+
+use async_channel::{self as channel, Receiver, Sender};
+use futures_core::Stream;
+use futures_timer::Delay;
+
+use std::cell::RefCell;
+use std::future::{poll_fn, Future};
+use std::io;
+use std::pin::Pin;
+use std::rc::Rc;
+use std::task::{Context, Poll};
+use std::time::Duration;
+
+/// How often the leader announces itself when locks are unavailable.
+const HEARTBEAT_INTERVAL: Duration = Duration::from_secs(1);
+
+/// How many heartbeats may be missed before another instance takes over.
+const MISSED_HEARTBEATS: u32 = 3;
+
+/// Elect a single leader among all tabs which take part in an election.
+///
+/// Leadership is held through an exclusive [`Lock`]. If the browser doesn't
+/// support the Web Locks API, the leader instead announces itself over a
+/// [`BroadcastChannel`], and another instance takes over once it goes quiet.
+/// Dropping the election gives up leadership.
+///
+/// # Examples
+///
+/// ```no_run
+/// use localghost::prelude::*;
+/// use localghost::{log, task::LeaderElection};
+/// use async_std::prelude::*;
+/// use std::io;
+///
+/// #[localghost::main]
+/// async fn main() -> io::Result<()> {
+///     let election = LeaderElection::new("sync")?;
+///     let mut changes = election.changes();
+///     while let Some(is_leader) = changes.next().await {
+///         log::info!("leader: {}", is_leader);
+///     }
+///     Ok(())
+/// }
+/// ```
+#[derive(Debug)]
+pub struct LeaderElection {
+    name: String,
+    shared: Rc<RefCell<Shared>>,
+    _stop: Sender<()>,
+}
+
+#[derive(Debug)]
+struct Shared {
+    name: String,
+    is_leader: bool,
+    subscribers: Vec<Sender<bool>>,
+}
+
+impl Shared {
+    fn set(&mut self, is_leader: bool) {
+        if self.is_leader == is_leader {
+            return;
+        }
+        self.is_leader = is_leader;
+        crate::log::debug!("LeaderElection({}): leader: {}", self.name, is_leader);
+        self.subscribers
+            .retain(|sender| sender.try_send(is_leader).is_ok());
+    }
+}
+
+impl LeaderElection {
+    /// Join the election with the given name.
+    ///
+    /// # Errors
+    ///
+    /// An error is returned if the browser supports neither the Web Locks API
+    /// nor `BroadcastChannel`.
+    pub fn new(name: &str) -> io::Result<Self> {
+        let shared = Rc::new(RefCell::new(Shared {
+            name: name.to_owned(),
+            is_leader: false,
+            subscribers: vec![],
+        }));
+        let (_stop, stop) = channel::bounded(1);
+        let key = format!("localghost:leader:{}", name);
+        if lock::is_supported() {
+            spawn_local(with_lock(key, shared.clone(), stop));
+        } else {
+            let channel = BroadcastChannel::open(&key)?;
+            spawn_local(with_heartbeats(channel, shared.clone(), stop));
+        }
+
+        Ok(Self {
+            name: name.to_owned(),
+            shared,
+            _stop,
+        })
+    }
+
+    /// Get the name of the election.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Returns `true` if this instance is currently the leader.
+    pub fn is_leader(&self) -> bool {
+        self.shared.borrow().is_leader
+    }
+
+    /// Create a stream which yields `true` when this instance becomes the
+    /// leader, and `false` when it loses leadership.
+    pub fn changes(&self) -> LeadershipChanges {
+        let (sender, receiver) = channel::unbounded();
+        self.shared.borrow_mut().subscribers.push(sender);
+        LeadershipChanges { receiver }
+    }
+}
+
+/// A stream of [`LeaderElection`] results.
+///
+/// This `struct` is created by the [`changes`] method on [`LeaderElection`].
+/// See its documentation for more.
+///
+/// [`changes`]: LeaderElection::changes
+#[pin_project::pin_project]
+#[derive(Debug)]
+#[must_use = "Streams do nothing unless polled"]
+pub struct LeadershipChanges {
+    #[pin]
+    receiver: Receiver<bool>,
+}
+
+impl Stream for LeadershipChanges {
+    type Item = bool;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = self.project();
+        this.receiver.poll_next(cx)
+    }
+}
+
+/// Wait for a future, or return `None` once the election is dropped.
+async fn until_stopped<F: Future>(fut: F, stop: &Receiver<()>) -> Option<F::Output> {
+    let mut fut = Box::pin(fut);
+    let mut stopped = Box::pin(stop.recv());
+    poll_fn(|cx| {
+        if stopped.as_mut().poll(cx).is_ready() {
+            return Poll::Ready(None);
+        }
+        fut.as_mut().poll(cx).map(Some)
+    })
+    .await
+}
+
+/// Lead for as long as we hold the lock, and queue up again if it's stolen.
+async fn with_lock(key: String, shared: Rc<RefCell<Shared>>, stop: Receiver<()>) {
+    loop {
+        let lock = match until_stopped(Lock::request(&key), &stop).await {
+            None => return,
+            Some(Ok(lock)) => lock,
+            Some(Err(err)) => {
+                crate::log::warn!("LeaderElection({}): {}", shared.borrow().name, err);
+                return;
+            }
+        };
+        shared.borrow_mut().set(true);
+        let stopped = until_stopped(lock.stolen(), &stop).await.is_none();
+        shared.borrow_mut().set(false);
+        if stopped {
+            return;
+        }
+    }
+}
+
+/// Lead by announcing ourselves periodically. Followers take over once the
+/// leader goes quiet, and when two leaders meet the one with the lowest id
+/// steps down.
+async fn with_heartbeats(
+    channel: BroadcastChannel,
+    shared: Rc<RefCell<Shared>>,
+    stop: Receiver<()>,
+) {
+    let id = (js_sys::Math::random() * u32::MAX as f64) as u32;
+    let mut missed = 0;
+    let mut delay = Delay::new(HEARTBEAT_INTERVAL);
+    loop {
+        let msg = match until_stopped(next_tick(&channel, &mut delay), &stop).await {
+            None | Some(Err(_)) => break,
+            Some(Ok(msg)) => msg,
+        };
+
+        let is_leader = shared.borrow().is_leader;
+        let msg = msg.as_deref().and_then(|msg| msg.split_once(':'));
+        match msg {
+            // The heartbeat interval elapsed.
+            None => {
+                delay.reset(HEARTBEAT_INTERVAL);
+                missed += 1;
+                if !is_leader && missed >= MISSED_HEARTBEATS {
+                    shared.borrow_mut().set(true);
+                }
+                if shared.borrow().is_leader {
+                    let _ = channel.send(&format!("heartbeat:{}", id));
+                }
+            }
+            Some(("heartbeat", other)) => {
+                missed = 0;
+                if is_leader && other.parse::<u32>().is_ok_and(|other| other > id) {
+                    shared.borrow_mut().set(false);
+                }
+            }
+            Some(("resign", _)) => missed = MISSED_HEARTBEATS,
+            Some(_) => {}
+        }
+    }
+
+    if shared.borrow().is_leader {
+        let _ = channel.send(&format!("resign:{}", id));
+        shared.borrow_mut().set(false);
+    }
+}
+
+/// Wait for the next message, or `None` once the heartbeat interval elapses.
+async fn next_tick(channel: &BroadcastChannel, delay: &mut Delay) -> io::Result<Option<String>> {
+    let mut recv = Box::pin(channel.recv());
+    poll_fn(|cx| {
+        if let Poll::Ready(res) = recv.as_mut().poll(cx) {
+            return Poll::Ready(res.map(Some));
+        }
+        Pin::new(&mut *delay).poll(cx).map(|()| Ok(None))
+    })
+    .await
+}
@@ -0,0 +1,271 @@
+use crate::prelude::*;
+use crate::task::spawn_local;
+use crate::utils::{window, ResultExt};
+
+use async_channel::{self as channel, Receiver};
+use js_sys::{Function, Object, Promise, Reflect};
+use wasm_bindgen::prelude::*;
+use wasm_bindgen::JsCast;
+use wasm_bindgen_futures::JsFuture;
+
+use std::io;
+
+// `web_sys` only exposes the Web Locks API behind `web_sys_unstable_apis`, so
+// we bind the single method we need ourselves.
+#[wasm_bindgen]
+extern "C" {
+    type LockManager;
+
+    #[wasm_bindgen(method)]
+    fn request(this: &LockManager, name: &str, options: &Object, callback: &Function) -> Promise;
+}
+
+/// How a [`Lock`] may be shared with other requests.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LockMode {
+    /// The lock is held by at most one request at a time.
+    Exclusive,
+    /// The lock may be held by many requests at once, as long as none of them
+    /// is exclusive.
+    Shared,
+}
+
+impl LockMode {
+    fn as_str(&self) -> &'static str {
+        match self {
+            LockMode::Exclusive => "exclusive",
+            LockMode::Shared => "shared",
+        }
+    }
+}
+
+/// A lock which is coordinated across all tabs and workers of an origin.
+///
+/// The lock is released when this guard is dropped.
+///
+/// [Read more](https://developer.mozilla.org/en-US/docs/Web/API/Web_Locks_API)
+///
+/// # Examples
+///
+/// ```no_run
+/// use localghost::prelude::*;
+/// use localghost::{log, task::Lock};
+/// use std::io;
+///
+/// #[localghost::main]
+/// async fn main() -> io::Result<()> {
+///     let lock = Lock::request("sync").await?;
+///     log::info!("holding the `{}` lock", lock.name());
+///     drop(lock);
+///     Ok(())
+/// }
+/// ```
+#[derive(Debug)]
+pub struct Lock {
+    name: String,
+    mode: LockMode,
+    release: Function,
+    stolen: Receiver<()>,
+}
+
+impl Lock {
+    /// Wait until the exclusive lock with the given name is available, and
+    /// acquire it.
+    ///
+    /// # Errors
+    ///
+    /// An error of kind `io::ErrorKind::Unsupported` is returned if the
+    /// browser doesn't support the Web Locks API.
+    pub async fn request(name: &str) -> io::Result<Self> {
+        let lock = Self::builder(name).request().await?;
+        Ok(lock.unwrap_throw())
+    }
+
+    /// Create a new builder to configure how the lock is requested.
+    pub fn builder(name: &str) -> LockBuilder {
+        LockBuilder {
+            name: name.to_owned(),
+            mode: LockMode::Exclusive,
+            if_available: false,
+            steal: false,
+        }
+    }
+
+    /// Get the name of the lock.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Get the mode the lock is held in.
+    pub fn mode(&self) -> LockMode {
+        self.mode
+    }
+
+    /// Wait until another request steals the lock.
+    ///
+    /// Once this resolves the lock is no longer held, even though this guard
+    /// still exists.
+    pub async fn stolen(&self) {
+        let _ = self.stolen.recv().await;
+    }
+}
+
+impl Drop for Lock {
+    fn drop(&mut self) {
+        let _ = self.release.call0(&JsValue::UNDEFINED);
+    }
+}
+
+/// Configure how a [`Lock`] is requested.
+///
+/// This `struct` is created by the [`builder`] method on [`Lock`]. See its
+/// documentation for more.
+///
+/// [`builder`]: Lock::builder
+#[derive(Debug)]
+pub struct LockBuilder {
+    name: String,
+    mode: LockMode,
+    if_available: bool,
+    steal: bool,
+}
+
+impl LockBuilder {
+    /// Set the mode to request the lock in. Defaults to
+    /// `LockMode::Exclusive`.
+    pub fn mode(mut self, mode: LockMode) -> Self {
+        self.mode = mode;
+        self
+    }
+
+    /// Only acquire the lock if it's available right away, rather than
+    /// waiting for it.
+    pub fn if_available(mut self, if_available: bool) -> Self {
+        self.if_available = if_available;
+        self
+    }
+
+    /// Release the lock from whoever currently holds it, and acquire it
+    /// right away. Can only be used in exclusive mode.
+    pub fn steal(mut self, steal: bool) -> Self {
+        self.steal = steal;
+        self
+    }
+
+    /// Request the lock.
+    ///
+    /// Returns `None` if `if_available` was set, and the lock was held
+    /// elsewhere.
+    ///
+    /// # Errors
+    ///
+    /// An error of kind `io::ErrorKind::Unsupported` is returned if the
+    /// browser doesn't support the Web Locks API, and
+    /// `io::ErrorKind::InvalidInput` if the options can't be combined.
+    pub async fn request(self) -> io::Result<Option<Lock>> {
+        let locks = manager()?;
+        let options = Object::new();
+        set(&options, "mode", &self.mode.as_str().into());
+        set(&options, "ifAvailable", &self.if_available.into());
+        set(&options, "steal", &self.steal.into());
+
+        // Waiting requests are aborted if this future is dropped. Signals
+        // can't be combined with the other options, but those never wait.
+        let mut controller = None;
+        if !self.if_available && !self.steal {
+            let abort = web_sys::AbortController::new().err_kind(io::ErrorKind::Other)?;
+            set(&options, "signal", &abort.signal());
+            controller = Some(abort);
+        }
+
+        let (sender, receiver) = channel::unbounded();
+        let mut pending = PendingRequest {
+            controller,
+            receiver,
+        };
+        let sender2 = sender.clone();
+        let callback = Closure::once(move |lock: JsValue| -> Promise {
+            if lock.is_null() {
+                let _ = sender2.try_send(Ok(None));
+                return Promise::resolve(&JsValue::UNDEFINED);
+            }
+            let mut release = None;
+            let held = Promise::new(&mut |resolve, _| release = Some(resolve));
+            let release = release.unwrap_throw();
+            // Nobody is waiting for the lock anymore, so give it back.
+            if sender2.try_send(Ok(Some(release.clone()))).is_err() {
+                let _ = release.call0(&JsValue::UNDEFINED);
+            }
+            held
+        });
+        let request = locks.request(&self.name, &options, callback.as_ref().unchecked_ref());
+
+        // The request settles once the lock is released, or rejects if it
+        // could not be acquired, was aborted or was stolen. The callback is
+        // kept alive until then, since it may not be called at all.
+        let (stolen_sender, stolen) = channel::bounded(1);
+        spawn_local(async move {
+            let res = JsFuture::from(request).await;
+            drop(callback);
+            if let Err(err) = res {
+                let _ = sender.try_send(Err(err));
+                let _ = stolen_sender.try_send(());
+            }
+        });
+
+        let res = pending.receiver.recv().await.unwrap_throw();
+        pending.controller = None;
+        let release = match res.dom_err_kind(io::ErrorKind::InvalidInput)? {
+            Some(release) => release,
+            None => return Ok(None),
+        };
+        Ok(Some(Lock {
+            name: self.name,
+            mode: self.mode,
+            release,
+            stolen,
+        }))
+    }
+}
+
+/// A lock request which is cancelled when dropped.
+struct PendingRequest {
+    controller: Option<web_sys::AbortController>,
+    receiver: Receiver<Result<Option<Function>, JsValue>>,
+}
+
+impl Drop for PendingRequest {
+    fn drop(&mut self) {
+        if let Some(controller) = &self.controller {
+            controller.abort();
+        }
+        // The lock may have been granted after all, without anyone left to
+        // hold it. Give it back, or it stays held until the page unloads.
+        while let Ok(res) = self.receiver.try_recv() {
+            if let Ok(Some(release)) = res {
+                let _ = release.call0(&JsValue::UNDEFINED);
+            }
+        }
+    }
+}
+
+/// Check whether the browser supports the Web Locks API.
+pub(crate) fn is_supported() -> bool {
+    manager().is_ok()
+}
+
+/// Get the `LockManager`, if the browser supports it.
+fn manager() -> io::Result<LockManager> {
+    let locks = Reflect::get(&window().navigator(), &"locks".into()).unwrap_or(JsValue::UNDEFINED);
+    if locks.is_undefined() {
+        return Err(io::Error::new(
+            io::ErrorKind::Unsupported,
+            "Unable to access LockManager.",
+        ));
+    }
+    Ok(locks.unchecked_into())
+}
+
+fn set(options: &Object, key: &str, value: &JsValue) {
+    Reflect::set(options, &key.into(), value).unwrap_throw();
+}
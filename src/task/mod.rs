@@ -3,9 +3,13 @@
 mod animation_frame;
 #[cfg(feature = "task-idle")]
 mod idle;
+mod leader;
+mod lock;
 mod spawn_local;
 
 pub use animation_frame::AnimationFrame;
 #[cfg(feature = "task-idle")]
 pub use idle::{Deadline, Idle};
+pub use leader::{LeaderElection, LeadershipChanges};
+pub use lock::{Lock, LockBuilder, LockMode};
 pub use spawn_local::{spawn_local, JoinHandle};
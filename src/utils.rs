@@ -1,9 +1,16 @@
 use crate::prelude::*;
 use std::io;
+use wasm_bindgen::JsCast;
 
 /// Convert a `Result<T, JsValue>` to an `io::Result<T>`.
 pub(crate) trait ResultExt<T> {
     fn err_kind(self, kind: io::ErrorKind) -> io::Result<T>;
+
+    /// Like `err_kind`, but uses the kind matching the name of a well-known
+    /// `DOMException` if there is one. `fallback` is used for other
+    /// `DOMException`s, and errors which aren't a `DOMException`, such as a
+    /// `TypeError` for an invalid argument, use `io::ErrorKind::InvalidInput`.
+    fn dom_err_kind(self, fallback: io::ErrorKind) -> io::Result<T>;
 }
 
 impl<T> ResultExt<T> for Result<T, wasm_bindgen::JsValue> {
@@ -14,6 +21,40 @@ impl<T> ResultExt<T> for Result<T, wasm_bindgen::JsValue> {
             io::Error::new(kind, msg)
         })
     }
+
+    fn dom_err_kind(self, fallback: io::ErrorKind) -> io::Result<T> {
+        match self {
+            Ok(val) => Ok(val),
+            Err(err) => {
+                let kind = match err.is_instance_of::<web_sys::DomException>() {
+                    true => dom_error_kind(&err).unwrap_or(fallback),
+                    false => io::ErrorKind::InvalidInput,
+                };
+                Err(err).err_kind(kind)
+            }
+        }
+    }
+}
+
+/// Get the `io::ErrorKind` matching the name of a `DOMException`.
+///
+/// Names whose meaning depends on the operation which threw them, such as
+/// `TypeMismatchError`, are not mapped.
+pub(crate) fn dom_error_kind(err: &JsValue) -> Option<io::ErrorKind> {
+    let kind = match err.dyn_ref::<web_sys::DomException>()?.name().as_str() {
+        "NotFoundError" => io::ErrorKind::NotFound,
+        "NotAllowedError" | "SecurityError" => io::ErrorKind::PermissionDenied,
+        "QuotaExceededError" => io::ErrorKind::StorageFull,
+        "NoModificationAllowedError" => io::ErrorKind::ResourceBusy,
+        "ReadOnlyError" => io::ErrorKind::PermissionDenied,
+        "ConstraintError" => io::ErrorKind::AlreadyExists,
+        "DataError" | "VersionError" => io::ErrorKind::InvalidInput,
+        "AbortError" => io::ErrorKind::Interrupted,
+        "TimeoutError" => io::ErrorKind::TimedOut,
+        "NetworkError" => io::ErrorKind::ConnectionAborted,
+        _ => return None,
+    };
+    Some(kind)
 }
 
 /// Get a `web_sys::Window`.
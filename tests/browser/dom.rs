@@ -0,0 +1,366 @@
+use async_std::prelude::*;
+use localghost::dom::{
+    self, body, Anchor, Element, FieldErrorKind, Form, Image, Input, Mutation, Mutations, Select,
+    VDom, VElement,
+};
+use localghost::html;
+use localghost::raw::web_sys;
+use serde::Deserialize;
+use std::cell::Cell;
+use std::convert::TryFrom;
+use std::io;
+use std::rc::Rc;
+use std::time::Duration;
+use wasm_bindgen_test::*;
+
+#[wasm_bindgen_test]
+fn query_selector_all() {
+    let list = Element::new("ul");
+    list.set_attr("id", "query-selector-all");
+    for class in &["done", "todo", "done"] {
+        let item = Element::new("li");
+        item.set_attr("class", class);
+        list.append(&item);
+    }
+    body().append(&list);
+
+    assert_eq!(dom::query_selector_all("#query-selector-all li").count(), 3);
+    assert_eq!(list.query_selector_all(".done").count(), 2);
+    assert_eq!(list.query_selector_all("p").count(), 0);
+
+    let item = list.query_selector(".todo").unwrap();
+    assert!(item.matches("li.todo"));
+    assert!(!item.matches(".done"));
+    assert!(item.closest("ul").unwrap().matches("#query-selector-all"));
+    assert!(item.closest("ol").is_none());
+}
+
+#[wasm_bindgen_test]
+fn tree_manipulation() {
+    let list = Element::new("ul");
+    let b = Element::with_text("li", "b");
+    list.append(&b);
+    list.prepend(Element::with_text("li", "a"));
+    b.after(Element::with_text("li", "d"));
+    b.next_sibling()
+        .unwrap()
+        .before(Element::with_text("li", "c"));
+
+    let texts = |list: &Element| -> Vec<_> { list.children().filter_map(|el| el.text()).collect() };
+    assert_eq!(texts(&list), ["a", "b", "c", "d"]);
+    assert_eq!(list.first_child().unwrap().text().unwrap(), "a");
+    assert_eq!(list.last_child().unwrap().text().unwrap(), "d");
+    assert_eq!(b.previous_sibling().unwrap().text().unwrap(), "a");
+    assert!(b.parent().unwrap().matches("ul"));
+    assert!(list.contains(&b));
+    assert!(!b.contains(&list));
+
+    let copy = list.clone_node(true);
+    assert_eq!(texts(&copy), ["a", "b", "c", "d"]);
+    assert_eq!(list.clone_node(false).children().count(), 0);
+
+    b.replace_with(Element::with_text("li", "B"));
+    list.last_child().unwrap().remove();
+    assert_eq!(texts(&list), ["a", "B", "c"]);
+    assert!(b.parent().is_none());
+    assert_eq!(texts(&copy), ["a", "b", "c", "d"]);
+}
+
+#[wasm_bindgen_test]
+fn typed_elements() {
+    let input = Input::new();
+    input.set_attr("type", "checkbox");
+    input.set_checked(true);
+    assert!(input.checked());
+    input.set_value("on");
+    assert_eq!(input.value(), "on");
+    input.set_disabled(true);
+    assert!(input.disabled());
+
+    let el: Element = input.into();
+    let input = el.downcast::<Input>().unwrap();
+    let el = Element::from(input).downcast::<Anchor>().unwrap_err();
+    assert_eq!(el.kind(), "input");
+
+    let select = Select::new();
+    select.set_attr("multiple", "");
+    for value in &["a", "b", "c"] {
+        let option = Element::with_text("option", value);
+        select.append(&option);
+    }
+    select.query_selector_all("option").for_each(|option| {
+        if option.text().unwrap() != "b" {
+            option.set_attr("selected", "");
+        }
+    });
+    assert_eq!(select.selected_options(), ["a", "c"]);
+
+    let found = select.query_selector("option").unwrap().parent().unwrap();
+    assert!(Select::try_from(found).is_ok());
+
+    let anchor = Anchor::new();
+    anchor.set_href("https://example.com/");
+    assert_eq!(anchor.href(), "https://example.com/");
+
+    let image = Image::new();
+    image.set_src("https://example.com/logo.png");
+    assert_eq!(image.src(), "https://example.com/logo.png");
+}
+
+#[wasm_bindgen_test]
+fn class_list_style_and_dataset() {
+    let el = Element::new("div");
+    let classes = el.class_list();
+    classes.add("card");
+    classes.add("active");
+    assert!(classes.contains("card"));
+    assert!(!classes.toggle("active"));
+    assert!(classes.toggle("hidden"));
+    classes.remove("card");
+    assert_eq!(el.attr("class").unwrap(), "hidden");
+    assert_eq!(classes.len(), 1);
+
+    let style = el.style();
+    style.set("background-color", "red");
+    style.set("--accent", "blue");
+    assert_eq!(style.get("background-color").unwrap(), "red");
+    assert_eq!(style.get("--accent").unwrap(), "blue");
+    style.remove("background-color");
+    assert!(style.get("background-color").is_none());
+
+    let dataset = el.dataset();
+    dataset.set("userId", 12);
+    assert_eq!(el.attr("data-user-id").unwrap(), "12");
+    assert_eq!(dataset.parse::<u32>("userId").unwrap(), Some(12));
+    assert!(dataset.parse::<bool>("userId").is_err());
+    dataset.remove("userId");
+    assert!(dataset.get("userId").is_none());
+}
+
+#[wasm_bindgen_test]
+fn html_macro() {
+    let clicks = Rc::new(Cell::new(0));
+    let clicks2 = clicks.clone();
+    let name = "world";
+    let el = html! {
+        <div class="greeting" data-count={2 + 1}>
+            <p>"Hello, " {name} "!"</p>
+            <input r#type="checkbox" />
+            <button on:click={move |_| clicks2.set(clicks2.get() + 1)}>"Click"</button>
+            {Element::with_text("span", "child")}
+        </div>
+    };
+
+    assert_eq!(el.kind(), "div");
+    assert!(el.matches(".greeting[data-count='3']"));
+    assert_eq!(
+        el.query_selector("p").unwrap().text().unwrap(),
+        "Hello, world!"
+    );
+    assert!(el.query_selector("input[type=checkbox]").is_some());
+    assert_eq!(el.last_child().unwrap().kind(), "span");
+
+    let button = el.query_selector("button").unwrap();
+    let target: &web_sys::EventTarget = button.as_ref();
+    let click = web_sys::Event::new("click").unwrap();
+    target.dispatch_event(&click).unwrap();
+    assert_eq!(clicks.get(), 1);
+}
+
+#[wasm_bindgen_test]
+fn vdom() {
+    let list = |items: &[&str]| {
+        VElement::new("ul").children(
+            items
+                .iter()
+                .map(|item| VElement::new("li").key(item).attr("id", item).text(item)),
+        )
+    };
+
+    let parent = Element::new("div");
+    let mut vdom = VDom::new(&parent);
+    vdom.render(list(&["a", "b", "c"]));
+    let ul = parent.first_child().unwrap();
+    let texts = |ul: &Element| -> Vec<_> { ul.children().filter_map(|el| el.text()).collect() };
+    assert_eq!(texts(&ul), ["a", "b", "c"]);
+
+    // Reordering keyed children moves the existing nodes.
+    ul.query_selector("#b").unwrap().set_attr("data-marker", "");
+    vdom.render(list(&["c", "b", "d"]));
+    assert_eq!(texts(&ul), ["c", "b", "d"]);
+    assert!(ul.children().nth(1).unwrap().matches("[data-marker]"));
+
+    // Attributes and text are patched in place.
+    vdom.render(VElement::new("ul").attr("class", "empty").text("none"));
+    assert_eq!(
+        parent.first_child().unwrap().attr("class").unwrap(),
+        "empty"
+    );
+    assert_eq!(parent.first_child().unwrap().text().unwrap(), "none");
+
+    // Listeners are swapped without registering twice.
+    let clicks = Rc::new(Cell::new(0));
+    for step in 1..=2 {
+        let clicks = clicks.clone();
+        vdom.render(VElement::new("button").on("click", move |_| clicks.set(clicks.get() + step)));
+    }
+    let button = parent.first_child().unwrap();
+    let target: &web_sys::EventTarget = button.as_ref();
+    target
+        .dispatch_event(&web_sys::Event::new("click").unwrap())
+        .unwrap();
+    assert_eq!(clicks.get(), 2);
+
+    vdom.render(VElement::new("button"));
+    target
+        .dispatch_event(&web_sys::Event::new("click").unwrap())
+        .unwrap();
+    assert_eq!(clicks.get(), 2);
+
+    drop(vdom);
+    assert!(parent.first_child().is_none());
+}
+
+#[wasm_bindgen_test]
+fn form_values() {
+    #[derive(Debug, Deserialize, PartialEq)]
+    struct Signup {
+        email: String,
+        age: Option<u32>,
+        terms: bool,
+        topics: Vec<String>,
+        plan: Option<String>,
+        colors: Vec<String>,
+    }
+
+    let form = html! {
+        <form>
+            <input name="email" r#type="email" required="" value="a@b.c" />
+            <input name="age" r#type="number" min="13" />
+            <input name="terms" r#type="checkbox" checked="" />
+            <input name="topics" r#type="checkbox" value="rust" checked="" />
+            <input name="topics" r#type="checkbox" value="web" />
+            <input name="plan" r#type="radio" value="free" />
+            <input name="plan" r#type="radio" value="pro" checked="" />
+            <select name="colors" multiple="">
+                <option value="red" selected="">"Red"</option>
+                <option value="blue">"Blue"</option>
+            </select>
+            <button r#type="submit" name="go">"Go"</button>
+        </form>
+    };
+    let form = form.downcast::<Form>().unwrap();
+
+    let signup: Signup = form.values().unwrap();
+    assert_eq!(
+        signup,
+        Signup {
+            email: "a@b.c".into(),
+            age: None,
+            terms: true,
+            topics: vec!["rust".into()],
+            plan: Some("pro".into()),
+            colors: vec!["red".into()],
+        }
+    );
+
+    let age = form.query_selector("[name=age]").unwrap();
+    let age = age.downcast::<Input>().unwrap();
+    age.set_value("7");
+    let err = form.values::<Signup>().unwrap_err();
+    assert_eq!(err.fields().len(), 1);
+    assert_eq!(err.fields()[0].name(), "age");
+    assert_eq!(err.fields()[0].kind(), FieldErrorKind::RangeUnderflow);
+
+    age.set_value("30");
+    let _validator = form.validator("email", |email| match email.ends_with(".c") {
+        true => Some("No .c domains".into()),
+        false => None,
+    });
+    let err = form.validate().unwrap_err();
+    assert_eq!(err.fields()[0].name(), "email");
+    assert_eq!(err.fields()[0].kind(), FieldErrorKind::Custom);
+    assert_eq!(err.fields()[0].message(), "No .c domains");
+}
+
+#[wasm_bindgen_test]
+async fn mutations() {
+    let el = Element::new("div");
+    let mut mutations = Mutations::builder()
+        .child_list(true)
+        .attribute_filter(vec!["class"])
+        .old_value(true)
+        .subtree(true)
+        .observe(&el)
+        .unwrap();
+
+    el.set_attr("id", "ignored");
+    el.set_attr("class", "a");
+    el.set_attr("class", "b");
+    el.append(Element::new("p"));
+
+    match mutations.next().await.unwrap() {
+        Mutation::Attribute {
+            name, old_value, ..
+        } => {
+            assert_eq!(name, "class");
+            assert!(old_value.is_none());
+        }
+        mutation => panic!("unexpected {:?}", mutation),
+    }
+    match mutations.next().await.unwrap() {
+        Mutation::Attribute { old_value, .. } => assert_eq!(old_value.unwrap(), "a"),
+        mutation => panic!("unexpected {:?}", mutation),
+    }
+    match mutations.next().await.unwrap() {
+        Mutation::ChildList { added, removed, .. } => {
+            assert_eq!(added.len(), 1);
+            assert!(removed.is_empty());
+        }
+        mutation => panic!("unexpected {:?}", mutation),
+    }
+
+    assert!(Mutations::builder().observe(&el).is_err());
+}
+
+#[wasm_bindgen_test]
+async fn wait_for_selector() {
+    let timeout = Duration::from_secs(1);
+    let el = Element::new("div");
+    el.set_attr("id", "wait-for-selector");
+    localghost::task::spawn_local(async move { body().append(el) });
+
+    let el = dom::wait_for_selector("#wait-for-selector", timeout)
+        .await
+        .unwrap();
+    assert!(el.matches("div"));
+
+    localghost::task::spawn_local(async move { el.remove() });
+    dom::wait_for_selector_removed("#wait-for-selector", timeout)
+        .await
+        .unwrap();
+
+    let err = dom::wait_for_selector("#never", Duration::from_millis(10))
+        .await
+        .unwrap_err();
+    assert_eq!(err.kind(), io::ErrorKind::TimedOut);
+    let err = dom::wait_for_selector("[", timeout).await.unwrap_err();
+    assert_eq!(err.kind(), io::ErrorKind::InvalidInput);
+}
+
+#[wasm_bindgen_test]
+async fn event_stream_buffers_events() {
+    use localghost::events::EventTarget;
+
+    let el = Element::new("button");
+    let mut clicks = el.on("click");
+    let target: &web_sys::EventTarget = el.as_ref();
+    for _ in 0..3 {
+        target
+            .dispatch_event(&web_sys::Event::new("click").unwrap())
+            .unwrap();
+    }
+    for _ in 0..3 {
+        assert!(clicks.next().await.is_some());
+    }
+}
@@ -0,0 +1,293 @@
+use async_std::prelude::*;
+use localghost::fs::{
+    self, IndexedDb, LocalStorage, MemoryStorage, Schema, SessionStorage, Storage, TransactionMode,
+};
+use wasm_bindgen_test::*;
+
+fn roundtrip(storage: &dyn Storage) {
+    storage.clear();
+    assert!(storage.is_empty());
+
+    storage.insert("foo", "bar").unwrap();
+    assert_eq!(storage.get("foo"), Some("bar".to_owned()));
+    assert_eq!(storage.keys(), vec!["foo".to_owned()]);
+    assert_eq!(storage.len(), 1);
+
+    assert_eq!(storage.remove("foo"), Some("bar".to_owned()));
+    assert_eq!(storage.get("foo"), None);
+    assert!(storage.is_empty());
+}
+
+#[wasm_bindgen_test]
+fn session_storage() {
+    roundtrip(&SessionStorage::open().unwrap());
+}
+
+#[wasm_bindgen_test]
+fn memory_storage() {
+    roundtrip(&MemoryStorage::new());
+}
+
+#[wasm_bindgen_test]
+fn iter_and_drain() {
+    let storage = MemoryStorage::new();
+    storage.insert("a", "1").unwrap();
+    storage.insert("b", "2").unwrap();
+
+    let entries: Vec<_> = storage.iter().collect();
+    assert_eq!(
+        entries,
+        vec![("a".into(), "1".into()), ("b".into(), "2".into())]
+    );
+
+    let drained: Vec<_> = storage.drain().collect();
+    assert_eq!(drained, entries);
+    assert!(storage.is_empty());
+}
+
+#[wasm_bindgen_test]
+fn namespace() {
+    let storage = MemoryStorage::new();
+    storage.insert("other", "x").unwrap();
+
+    let app = storage.namespace("app:");
+    app.insert("theme", "dark").unwrap();
+    assert_eq!(storage.get("app:theme"), Some("dark".to_owned()));
+    assert_eq!(app.keys(), vec!["theme".to_owned()]);
+
+    app.clear();
+    assert!(app.is_empty());
+    assert_eq!(storage.keys(), vec!["other".to_owned()]);
+}
+
+#[wasm_bindgen_test]
+fn json() {
+    let storage = MemoryStorage::new();
+    storage.insert_json("list", &vec![1, 2, 3]).unwrap();
+    assert_eq!(
+        storage.get_json::<Vec<u32>>("list").unwrap(),
+        Some(vec![1, 2, 3])
+    );
+    assert_eq!(storage.get_json::<Vec<u32>>("missing").unwrap(), None);
+
+    storage.insert("broken", "{").unwrap();
+    let err = storage.get_json::<Vec<u32>>("broken").unwrap_err();
+    assert_eq!(err.kind(), std::io::ErrorKind::InvalidData);
+}
+
+#[wasm_bindgen_test]
+fn versioned() {
+    let storage = MemoryStorage::new();
+    storage.insert_json("count", &1).unwrap();
+
+    let schema = Schema::new(2)
+        .migration(0, |value| (value.as_u64().unwrap() * 10).into())
+        .migration(1, |value| (value.as_u64().unwrap() + 1).into());
+    assert_eq!(
+        storage.get_versioned::<u64>("count", &schema).unwrap(),
+        Some(11)
+    );
+
+    // The migrated value was written back.
+    assert_eq!(
+        storage.get_versioned::<u64>("count", &schema).unwrap(),
+        Some(11)
+    );
+
+    let err = storage
+        .get_versioned::<u64>("count", &Schema::new(1))
+        .unwrap_err();
+    assert_eq!(err.kind(), std::io::ErrorKind::InvalidData);
+
+    // Unversioned data which looks like an envelope is still version 0.
+    let value = serde_json::json!({ "data": 1, "version": 1 });
+    storage.insert_json("lookalike", &value).unwrap();
+    let schema = Schema::new(1).migration(0, |value| value["data"].clone());
+    assert_eq!(
+        storage.get_versioned::<u64>("lookalike", &schema).unwrap(),
+        Some(1)
+    );
+}
+
+#[wasm_bindgen_test]
+fn open_with_fallback() {
+    let storage = LocalStorage::open_with_fallback();
+    assert!(storage.is_persistent());
+    roundtrip(&storage);
+
+    // Checking for a quota doesn't write to local storage, where other tabs
+    // would see it.
+    storage.clear();
+    let storage = LocalStorage::open_with_fallback();
+    assert!(storage.is_persistent());
+    assert!(storage.is_empty());
+}
+
+#[wasm_bindgen_test]
+async fn indexed_db() {
+    IndexedDb::delete("localghost-test").await.unwrap();
+    let db = IndexedDb::builder("localghost-test")
+        .upgrade(1, |upgrade| {
+            upgrade.create_object_store("items", None, false)?;
+            Ok(())
+        })
+        .open()
+        .await
+        .unwrap();
+    assert_eq!(db.version(), 1);
+    assert_eq!(db.object_store_names(), vec!["items".to_owned()]);
+
+    let tx = db
+        .transaction(&["items"], TransactionMode::ReadWrite)
+        .unwrap();
+    let items = tx.object_store("items").unwrap();
+    items.put_with_key("one", &1).await.unwrap();
+    items.put_with_key("two", &2).await.unwrap();
+    assert_eq!(
+        items.get::<_, String>(&1).await.unwrap(),
+        Some("one".into())
+    );
+    assert_eq!(items.get::<_, String>(&3).await.unwrap(), None);
+    assert_eq!(items.count().await.unwrap(), 2);
+    tx.commit().await.unwrap();
+
+    let tx = db
+        .transaction(&["items"], TransactionMode::ReadOnly)
+        .unwrap();
+    let items = tx.object_store("items").unwrap();
+    let err = items.put_with_key("three", &3).await.unwrap_err();
+    assert_eq!(err.kind(), std::io::ErrorKind::PermissionDenied);
+    let mut cursor = items.cursor::<u32, String>().unwrap();
+    let mut entries = vec![];
+    while let Some(entry) = cursor.next().await {
+        entries.push(entry.unwrap());
+    }
+    assert_eq!(entries, vec![(1, "one".into()), (2, "two".into())]);
+}
+
+#[wasm_bindgen_test]
+async fn private_file_system() {
+    let _ = fs::remove_dir_all("localghost-test").await;
+    fs::create_dir_all("localghost-test/nested").await.unwrap();
+    fs::write("localghost-test/a.txt", "hello").await.unwrap();
+    assert_eq!(
+        fs::read_to_string("localghost-test/a.txt").await.unwrap(),
+        "hello"
+    );
+
+    let meta = fs::metadata("localghost-test/a.txt").await.unwrap();
+    assert!(meta.is_file());
+    assert_eq!(meta.len(), 5);
+    assert!(fs::metadata("localghost-test/nested")
+        .await
+        .unwrap()
+        .is_dir());
+
+    fs::rename("localghost-test/a.txt", "localghost-test/nested/b.txt")
+        .await
+        .unwrap();
+    let err = fs::read("localghost-test/a.txt").await.unwrap_err();
+    assert_eq!(err.kind(), std::io::ErrorKind::NotFound);
+
+    let mut entries = fs::read_dir("localghost-test/nested").await.unwrap();
+    let entry = entries.next().await.unwrap().unwrap();
+    assert_eq!(entry.path(), "localghost-test/nested/b.txt");
+    assert!(entries.next().await.is_none());
+
+    let err = fs::remove_dir("localghost-test").await.unwrap_err();
+    assert_eq!(err.kind(), std::io::ErrorKind::DirectoryNotEmpty);
+
+    // Invalid names are rejected with a `TypeError`, not a `DOMException`.
+    let err = fs::write("localghost-test/a\\b.txt", "hello")
+        .await
+        .unwrap_err();
+    assert_eq!(err.kind(), std::io::ErrorKind::InvalidInput);
+    fs::remove_dir_all("localghost-test").await.unwrap();
+}
+
+#[wasm_bindgen_test]
+async fn file() {
+    use localghost::raw::{js_sys, web_sys};
+
+    let parts = js_sys::Array::of1(&"hello world".into());
+    let file = web_sys::File::new_with_str_sequence(&parts, "hello.txt").unwrap();
+    let file = fs::File::from_raw(file);
+    assert_eq!(file.name(), "hello.txt");
+    assert_eq!(file.size(), 11);
+    assert_eq!(file.read_to_string().await.unwrap(), "hello world");
+
+    let mut chunks = file.chunks(4);
+    let mut bytes = vec![];
+    while let Some(chunk) = chunks.next().await {
+        let chunk = chunk.unwrap();
+        assert!(chunk.len() <= 4);
+        bytes.extend(chunk);
+    }
+    assert_eq!(bytes, b"hello world");
+}
+
+#[wasm_bindgen_test]
+async fn persisted() {
+    LocalStorage::open().unwrap().remove("localghost-test");
+    let count = fs::Persisted::new("localghost-test", 0_u32).unwrap();
+    assert_eq!(count.get(), 0);
+    count.update(|count| *count += 1).unwrap();
+    assert_eq!(count.get(), 1);
+
+    let reloaded = fs::Persisted::new("localghost-test", 0_u32).unwrap();
+    assert_eq!(reloaded.get(), 1);
+
+    // Changes other tabs make to the same key are applied.
+    let mut changes = count.changes();
+    let local = localghost::raw::web_sys::window()
+        .unwrap()
+        .local_storage()
+        .unwrap()
+        .unwrap();
+    dispatch_storage_event(&local, "localghost-other", "3");
+    dispatch_storage_event(&local, "localghost-test", "5");
+    assert_eq!(changes.next().await, Some(5));
+    assert_eq!(count.get(), 5);
+}
+
+/// Dispatch a `storage` event, as if another tab had changed `storage`.
+fn dispatch_storage_event(storage: &localghost::raw::web_sys::Storage, key: &str, value: &str) {
+    use localghost::raw::{js_sys, wasm_bindgen::JsCast, web_sys};
+
+    let window = web_sys::window().unwrap();
+    let init = js_sys::Object::new();
+    js_sys::Reflect::set(&init, &"key".into(), &key.into()).unwrap();
+    js_sys::Reflect::set(&init, &"newValue".into(), &value.into()).unwrap();
+    js_sys::Reflect::set(&init, &"storageArea".into(), storage).unwrap();
+    let class = js_sys::Reflect::get(&window, &"StorageEvent".into()).unwrap();
+    let args = js_sys::Array::of2(&"storage".into(), &init);
+    let class: &js_sys::Function = class.unchecked_ref();
+    let ev = js_sys::Reflect::construct(class, &args).unwrap();
+    window.dispatch_event(ev.unchecked_ref()).unwrap();
+}
+
+#[wasm_bindgen_test]
+async fn storage_changes() {
+    use localghost::fs::StorageArea;
+    use localghost::raw::web_sys;
+
+    let window = web_sys::window().unwrap();
+    let local = window.local_storage().unwrap().unwrap();
+    let session = window.session_storage().unwrap().unwrap();
+    let mut changes = LocalStorage::open().unwrap().changes();
+
+    // Several changes may arrive before the stream is polled, and changes to
+    // other storage areas are skipped.
+    dispatch_storage_event(&local, "a", "1");
+    dispatch_storage_event(&session, "b", "2");
+    dispatch_storage_event(&local, "c", "3");
+
+    let change = changes.next().await.unwrap();
+    assert_eq!(change.key(), Some("a"));
+    assert_eq!(change.new_value(), Some("1"));
+    assert_eq!(change.old_value(), None);
+    assert_eq!(change.area(), StorageArea::Local);
+    assert!(!change.is_clear());
+    let change = changes.next().await.unwrap();
+    assert_eq!(change.key(), Some("c"));
+}
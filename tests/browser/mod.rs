@@ -1,4 +1,7 @@
 mod document_ready;
+mod dom;
+mod fs;
 mod log;
 mod net;
+mod reactive;
 mod task;
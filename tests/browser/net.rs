@@ -5,3 +5,325 @@ fn beacon_can_send() {
     let beacon = localghost::net::Beacon::new("https://example.com".to_string());
     beacon.send(&mut b"hello world".to_owned());
 }
+
+#[wasm_bindgen_test]
+async fn rpc_over_message_ports() {
+    use localghost::net::{MessagePort, Rpc, RpcError};
+
+    let (a, b) = MessagePort::pair();
+    let (a, b) = (Rpc::new(a), Rpc::new(b));
+    b.handle("add", |(x, y): (i64, i64)| async move {
+        Ok::<_, RpcError>(x + y)
+    });
+
+    let sum: i64 = a.call("add", (1, 2)).await.unwrap();
+    assert_eq!(sum, 3);
+
+    let err = a.call::<_, i64>("missing", ()).await.unwrap_err();
+    let err = err.into_inner().unwrap().downcast::<RpcError>().unwrap();
+    assert_eq!(err.code(), RpcError::method_not_found().code());
+}
+
+#[wasm_bindgen_test]
+async fn rpc_over_broadcast_channel() {
+    use async_std::prelude::*;
+    use localghost::net::{BroadcastChannel, Rpc, RpcError};
+
+    let open = || Rpc::new(BroadcastChannel::open("rpc-test").unwrap());
+    let (a, b, server) = (open(), open(), open());
+    server.handle("echo", |s: String| async move { Ok::<_, RpcError>(s) });
+
+    // Both callers number their calls the same way, and every peer sees every
+    // message. Each must still get its own result, and `a` and `b` must not
+    // answer requests they have no handler for.
+    let (x, y) = a
+        .call::<_, String>("echo", "a")
+        .join(b.call::<_, String>("echo", "b"))
+        .await;
+    assert_eq!(x.unwrap(), "a");
+    assert_eq!(y.unwrap(), "b");
+}
+
+#[wasm_bindgen_test]
+async fn request_with_invalid_body() {
+    use localghost::net::Request;
+
+    let mut req = Request::get("https://example.com");
+    req.set_body("hello");
+    let err = req.send().await.unwrap_err();
+    assert_eq!(err.kind(), std::io::ErrorKind::InvalidInput);
+}
+
+#[wasm_bindgen_test]
+async fn outbox_persists_and_retries() {
+    use async_std::prelude::*;
+    use localghost::fs::LocalStorage;
+    use localghost::net::{Outbox, OutboxStatus, Request};
+
+    LocalStorage::open()
+        .unwrap()
+        .remove("localghost:outbox:outbox-test");
+
+    // Nothing listens on this port, so every attempt fails.
+    let outbox = Outbox::open("outbox-test").unwrap();
+    let mut events = outbox.events();
+    let id = outbox.push(Request::post("http://localhost:1/")).unwrap();
+    let statuses = [OutboxStatus::Sending, OutboxStatus::Retrying { attempt: 1 }];
+    for status in &statuses {
+        let ev = events.next().await.unwrap();
+        assert_eq!(ev.id(), id);
+        assert_eq!(ev.status(), status);
+    }
+
+    // The queue survives reopening the outbox, along with its attempts.
+    drop(outbox);
+    let outbox = Outbox::open("outbox-test").unwrap();
+    assert_eq!(outbox.len(), 1);
+    outbox.set_max_attempts(2);
+    let mut events = outbox.events();
+    assert_eq!(
+        events.next().await.unwrap().status(),
+        &OutboxStatus::Sending
+    );
+    assert_eq!(events.next().await.unwrap().status(), &OutboxStatus::Failed);
+    assert!(outbox.is_empty());
+
+    // Invalid requests fail without being retried.
+    let mut req = Request::get("http://localhost:1/");
+    req.set_body("hello");
+    outbox.push(req).unwrap();
+    let statuses = [OutboxStatus::Sending, OutboxStatus::Failed];
+    for status in &statuses {
+        assert_eq!(events.next().await.unwrap().status(), status);
+    }
+}
+
+#[wasm_bindgen_test]
+async fn outbox_shared_between_instances() {
+    use async_std::prelude::*;
+    use localghost::fs::LocalStorage;
+    use localghost::net::{Outbox, OutboxStatus, Request};
+    use std::time::Duration;
+
+    LocalStorage::open()
+        .unwrap()
+        .remove("localghost:outbox:outbox-shared");
+
+    // Two instances stand in for two tabs sharing an outbox.
+    let a = Outbox::open("outbox-shared").unwrap();
+    let b = Outbox::open("outbox-shared").unwrap();
+    let mut events = a.events().merge(b.events());
+    let first = a.push(Request::post("data:,first")).unwrap();
+    let second = b.push(Request::post("data:,second")).unwrap();
+    assert_ne!(first, second);
+
+    // Each request is sent exactly once, by either instance.
+    let mut sent = vec![];
+    while sent.len() < 2 {
+        let ev = events.next().await.unwrap();
+        if let OutboxStatus::Sent(status) = ev.status() {
+            assert_eq!(*status, 200);
+            sent.push(ev.id());
+        }
+    }
+    sent.sort_unstable();
+    assert_eq!(sent, [first, second]);
+    let extra = async {
+        loop {
+            let ev = events.next().await.unwrap();
+            if let OutboxStatus::Sent(_) = ev.status() {
+                return ev;
+            }
+        }
+    };
+    assert!(extra.timeout(Duration::from_millis(100)).await.is_err());
+    assert!(a.is_empty() && b.is_empty());
+}
+
+/// Store a response in a cache, bypassing `localghost`.
+async fn cache_response(cache: &str, url: &str, headers: &[(&str, &str)], body: &str) {
+    use localghost::raw::wasm_bindgen::JsCast;
+    use localghost::raw::web_sys;
+    use wasm_bindgen_futures::JsFuture;
+
+    let caches = web_sys::window().unwrap().caches().unwrap();
+    let cache = JsFuture::from(caches.open(cache)).await.unwrap();
+    let cache: web_sys::Cache = cache.unchecked_into();
+    let init = web_sys::ResponseInit::new();
+    let raw_headers = web_sys::Headers::new().unwrap();
+    for (name, value) in headers {
+        raw_headers.set(name, value).unwrap();
+    }
+    init.set_headers_headers(&raw_headers);
+    let res = web_sys::Response::new_with_opt_str_and_init(Some(body), &init).unwrap();
+    JsFuture::from(cache.put_with_str(url, &res)).await.unwrap();
+}
+
+#[wasm_bindgen_test]
+async fn cache() {
+    use localghost::net::Cache;
+
+    let url = "http://localhost:1/cache";
+    cache_response("cache-test", url, &[], "hello").await;
+    let cache = Cache::open("cache-test").await.unwrap();
+    let res = cache.match_url(url).await.unwrap().unwrap();
+    assert_eq!(res.body_string().await.unwrap(), "hello");
+    assert!(cache.keys().await.unwrap().iter().any(|key| key == url));
+
+    assert!(cache.delete(url).await.unwrap());
+    assert!(cache.match_url(url).await.unwrap().is_none());
+}
+
+#[wasm_bindgen_test]
+async fn cache_strategy() {
+    use localghost::net::{CacheStrategy, Request};
+    use localghost::raw::js_sys::Date;
+
+    // Nothing listens on this port, so these requests can only be served from
+    // the cache.
+    let send = |method: &str, url: &str, strategy| {
+        let mut req = Request::new(method, url);
+        req.set_cache_strategy("cache-strategy-test", strategy);
+        req.send()
+    };
+
+    let now = String::from(Date::new_0().to_utc_string());
+    let url = "http://localhost:1/fresh";
+    let headers = [("cache-control", "max-age=60"), ("date", now.as_str())];
+    cache_response("cache-strategy-test", url, &headers, "fresh").await;
+    let res = send("get", url, CacheStrategy::CacheFirst).await.unwrap();
+    assert_eq!(res.body_string().await.unwrap(), "fresh");
+
+    // Cross-origin responses don't expose `Date`, so the time they were
+    // stored is used instead.
+    let url = "http://localhost:1/no-date";
+    let stored = Date::now().to_string();
+    let headers = [
+        ("cache-control", "max-age=60"),
+        ("x-localghost-fetched-at", stored.as_str()),
+    ];
+    cache_response("cache-strategy-test", url, &headers, "no date").await;
+    let res = send("GET", url, CacheStrategy::CacheFirst).await.unwrap();
+    assert_eq!(res.body_string().await.unwrap(), "no date");
+
+    // Stale responses are still used when the network is unreachable.
+    let url = "http://localhost:1/stale";
+    let headers = [("cache-control", "max-age=0"), ("date", now.as_str())];
+    cache_response("cache-strategy-test", url, &headers, "stale").await;
+    for strategy in [CacheStrategy::CacheFirst, CacheStrategy::NetworkFirst] {
+        let res = send("GET", url, strategy).await.unwrap();
+        assert_eq!(res.body_string().await.unwrap(), "stale");
+    }
+
+    let url = "http://localhost:1/uncached";
+    assert!(send("GET", url, CacheStrategy::CacheFirst).await.is_err());
+}
+
+#[wasm_bindgen_test]
+async fn query_cache_coalesces_requests() {
+    use async_std::prelude::*;
+    use localghost::net::{QueryCache, Request};
+    use std::time::Duration;
+
+    let url = "data:text/plain,hello";
+    let cache = QueryCache::with_stale_time(Duration::from_secs(60));
+    let mut updates = cache.subscribe(Request::get(url));
+
+    // Every update to the entry reaches the subscriber, so a single update
+    // means both fetches shared the subscriber's request. Methods are
+    // case-insensitive.
+    let (a, b) = cache
+        .fetch(Request::get(url))
+        .join(cache.fetch(Request::new("get", url)))
+        .await;
+    assert_eq!(a.unwrap().body_string().await.unwrap(), "hello");
+    assert_eq!(b.unwrap().body_string().await.unwrap(), "hello");
+
+    let update = updates.next().await.unwrap().unwrap();
+    assert_eq!(update.body_string().await.unwrap(), "hello");
+    let next = updates.next().timeout(Duration::from_millis(50)).await;
+    assert!(next.is_err());
+}
+
+#[wasm_bindgen_test]
+async fn query_cache_evicts_entries() {
+    use localghost::net::{QueryCache, Request};
+    use localghost::raw::js_sys::{global, Array, Function, Reflect};
+    use localghost::raw::wasm_bindgen::JsCast;
+    use localghost::raw::web_sys::Blob;
+    use std::time::Duration;
+
+    // Blob urls stop working once revoked, so a response for one can only be
+    // served from the cache afterwards.
+    let url_class = Reflect::get(&global(), &"URL".into()).unwrap();
+    let call = |name: &str, arg| {
+        let f: Function = Reflect::get(&url_class, &name.into())
+            .unwrap()
+            .unchecked_into();
+        f.call1(&url_class, &arg).unwrap()
+    };
+    let blob = Blob::new_with_str_sequence(&Array::of1(&"a".into())).unwrap();
+    let a = call("createObjectURL", blob.into()).as_string().unwrap();
+
+    let cache = QueryCache::with_stale_time(Duration::from_secs(60));
+    cache.set_max_entries(1);
+    cache.fetch(Request::get(&a)).await.unwrap();
+    call("revokeObjectURL", a.as_str().into());
+    let res = cache.fetch(Request::get(&a)).await.unwrap();
+    assert_eq!(res.body_string().await.unwrap(), "a");
+
+    // Caching another response evicts the first one.
+    cache
+        .fetch(Request::get("data:text/plain,b"))
+        .await
+        .unwrap();
+    assert!(cache.fetch(Request::get(&a)).await.is_err());
+}
+
+#[wasm_bindgen_test]
+async fn reconnecting_websocket_backs_off() {
+    use async_std::prelude::*;
+    use localghost::net::{ConnectionState, ReconnectingWebSocket};
+    use std::time::Duration;
+
+    let (min, max) = (Duration::from_millis(10), Duration::from_millis(15));
+    let socket = ReconnectingWebSocket::connect("ws://localhost:1");
+    socket.set_backoff(min, max);
+    socket.send("buffered until connected");
+
+    let mut states = socket.state_changes();
+    assert_eq!(states.next().await, Some(ConnectionState::Connecting));
+    let expected = [
+        ConnectionState::Reconnecting {
+            attempt: 1,
+            delay: min,
+        },
+        ConnectionState::Connecting,
+        ConnectionState::Reconnecting {
+            attempt: 2,
+            delay: max,
+        },
+        ConnectionState::Connecting,
+    ];
+    for state in expected.iter() {
+        assert_eq!(states.next().await.as_ref(), Some(state));
+    }
+
+    // An attempt may fail before the instance is closed.
+    socket.close();
+    let closed = async {
+        while let Some(state) = states.next().await {
+            if state == ConnectionState::Closed {
+                break;
+            }
+        }
+    };
+    closed.timeout(Duration::from_secs(1)).await.unwrap();
+}
+
+#[wasm_bindgen_test]
+async fn websocket_connect_fails() {
+    use localghost::net::WebSocket;
+
+    assert!(WebSocket::connect("ws://localhost:1").await.is_err());
+}
@@ -0,0 +1,118 @@
+use localghost::dom::{Element, VElement, VNode};
+use localghost::reactive::{self, Effect, Memo, Signal};
+use std::cell::Cell;
+use std::rc::Rc;
+use wasm_bindgen_test::*;
+
+#[wasm_bindgen_test]
+fn signals() {
+    let first = Signal::new(1);
+    let second = Signal::new(10);
+    let use_first = Signal::new(true);
+
+    let (a, b, c) = (first.clone(), second.clone(), use_first.clone());
+    let picked = Memo::new(move || if c.get() { a.get() } else { b.get() });
+
+    let runs = Rc::new(Cell::new(0));
+    let seen = Rc::new(Cell::new(0));
+    let (runs2, seen2, picked2) = (runs.clone(), seen.clone(), picked.clone());
+    let effect = Effect::new(move || {
+        runs2.set(runs2.get() + 1);
+        seen2.set(picked2.get());
+    });
+    assert_eq!((runs.get(), seen.get()), (1, 1));
+
+    // Updates are batched until the queue is flushed.
+    first.set(2);
+    first.set(3);
+    assert_eq!(picked.get(), 3);
+    assert_eq!(seen.get(), 1);
+    reactive::flush();
+    assert_eq!((runs.get(), seen.get()), (2, 3));
+
+    // Signals which are no longer read don't trigger updates.
+    use_first.set(false);
+    reactive::flush();
+    assert_eq!((runs.get(), seen.get()), (3, 10));
+    first.set(4);
+    reactive::flush();
+    assert_eq!(runs.get(), 3);
+
+    // Memos only notify when their value changes.
+    second.set(10);
+    reactive::flush();
+    assert_eq!(runs.get(), 3);
+
+    drop(effect);
+    second.set(11);
+    reactive::flush();
+    assert_eq!(runs.get(), 3);
+}
+
+#[wasm_bindgen_test]
+fn bindings() {
+    let count = Signal::new(0);
+    let items = Signal::new(vec!["a", "b"]);
+    let el = Element::new("div");
+
+    let c1 = count.clone();
+    let _text = el.bind_text(move || format!("count: {}", c1.get()));
+    let c2 = count.clone();
+    let _attr = el.bind_attr("data-count", move || Some(c2.get().to_string()));
+    let c3 = count.clone();
+    let _class = el.bind_class("odd", move || c3.get() % 2 == 1);
+    assert_eq!(el.text().unwrap(), "count: 0");
+
+    count.set(1);
+    reactive::flush();
+    assert_eq!(el.text().unwrap(), "count: 1");
+    assert_eq!(el.attr("data-count").unwrap(), "1");
+    assert!(el.class_list().contains("odd"));
+
+    let list = Element::new("ul");
+    let items2 = items.clone();
+    let _children = list.bind_children(move || {
+        items2
+            .get()
+            .into_iter()
+            .map(|item| VNode::from(VElement::new("li").key(item).text(item)))
+            .collect()
+    });
+    let first = list.first_child().unwrap();
+    first.set_attr("data-marker", "");
+    items.set(vec!["c", "a"]);
+    reactive::flush();
+    let texts: Vec<_> = list.children().filter_map(|el| el.text()).collect();
+    assert_eq!(texts, ["c", "a"]);
+    assert!(list.last_child().unwrap().matches("[data-marker]"));
+}
+
+#[wasm_bindgen_test]
+fn effects_writing_signals() {
+    // An effect which writes to a signal it reads doesn't re-run itself.
+    let count = Signal::new(0);
+    let runs = Rc::new(Cell::new(0));
+    let (count2, runs2) = (count.clone(), runs.clone());
+    let _effect = Effect::new(move || {
+        runs2.set(runs2.get() + 1);
+        count2.set(count2.get() + 1);
+    });
+    assert_eq!((runs.get(), count.get()), (1, 1));
+    reactive::flush();
+    assert_eq!(runs.get(), 1);
+
+    count.set(10);
+    reactive::flush();
+    assert_eq!((runs.get(), count.get()), (2, 11));
+
+    // Effects which keep queuing each other are eventually dropped.
+    let (a, b) = (Signal::new(0), Signal::new(0));
+    let (a1, b1) = (a.clone(), b.clone());
+    let _ping = Effect::new(move || b1.set(a1.get() + 1));
+    let (a2, b2) = (a.clone(), b.clone());
+    let _pong = Effect::new(move || a2.set(b2.get() + 1));
+    reactive::flush();
+    let settled = a.get();
+    reactive::flush();
+    assert_eq!(a.get(), settled);
+}
@@ -1,7 +1,9 @@
 use std::sync::{Arc, Mutex};
+use std::time::Duration;
 use wasm_bindgen_test::*;
 
-use localghost::task::AnimationFrame;
+use async_std::prelude::*;
+use localghost::task::{AnimationFrame, LeaderElection, Lock, LockMode};
 
 #[wasm_bindgen_test]
 async fn animation_loop() {
@@ -23,6 +25,53 @@ async fn spawn_local() {
     assert_eq!(handle.await, 12);
 }
 
+#[wasm_bindgen_test]
+async fn lock() {
+    let lock = Lock::request("lock").await.unwrap();
+    assert_eq!(lock.name(), "lock");
+    assert_eq!(lock.mode(), LockMode::Exclusive);
+
+    let other = Lock::builder("lock").if_available(true).request().await;
+    assert!(other.unwrap().is_none());
+
+    // The lock is released asynchronously, so wait for it rather than
+    // requesting it only if it's available.
+    drop(lock);
+    let shared = Lock::builder("lock")
+        .mode(LockMode::Shared)
+        .request()
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(shared.mode(), LockMode::Shared);
+
+    // A request which is dropped while waiting doesn't hold on to the lock.
+    let waiting = Lock::request("lock")
+        .timeout(Duration::from_millis(10))
+        .await;
+    assert!(waiting.is_err());
+    drop(shared);
+    let lock = Lock::request("lock").timeout(Duration::from_secs(1)).await;
+    assert!(lock.unwrap().is_ok());
+}
+
+#[wasm_bindgen_test]
+async fn leader_election() {
+    let election = LeaderElection::new("election").unwrap();
+    let mut changes = election.changes();
+    assert_eq!(changes.next().await, Some(true));
+    assert!(election.is_leader());
+
+    let lock = Lock::builder("localghost:leader:election")
+        .steal(true)
+        .request()
+        .await
+        .unwrap();
+    assert_eq!(changes.next().await, Some(false));
+    drop(lock);
+    assert_eq!(changes.next().await, Some(true));
+}
+
 // #[wasm_bindgen_test]
 // async fn spawn_idle() {
 //     let handle = localghost::task::spawn_idle(|| {
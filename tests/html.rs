@@ -0,0 +1,7 @@
+#![cfg(not(target_arch = "wasm32"))]
+
+#[test]
+fn html_errors() {
+    let t = trybuild::TestCases::new();
+    t.compile_fail("tests/ui/html-*.rs");
+}
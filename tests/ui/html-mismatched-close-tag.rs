@@ -0,0 +1,9 @@
+use localghost::html;
+
+fn main() {
+    let _ = html! {
+        <div>
+            <p>"Hello"</span>
+        </div>
+    };
+}
@@ -0,0 +1,7 @@
+use localghost::html;
+
+fn main() {
+    let _ = html! {
+        <button on:click="alert('clicked')">"Click me"</button>
+    };
+}
@@ -0,0 +1,8 @@
+use localghost::html;
+
+fn main() {
+    let _ = html! {
+        <div>
+            <p>"Hello"</p>
+    };
+}
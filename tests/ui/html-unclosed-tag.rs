@@ -0,0 +1,7 @@
+use localghost::html;
+
+fn main() {
+    let _ = html! {
+        <div class="greeting"
+    };
+}